@@ -3,24 +3,37 @@ use serde_json::Value;
 use std::error::Error;
 use std::fmt::Write as FmtWrite;
 use std::fs::read_dir;
-use std::path::{Path, PathBuf};
-use std::{fs, io};
+use std::path::Path;
+use std::{env, fs, io};
 use tinytemplate::TinyTemplate;
 
-fn days(input_dir: &str) -> io::Result<Vec<u32>> {
-    let mut days = read_dir(input_dir)?
+/// Days having a `dayNN.rs` solution, whether their input is there or not.
+fn days(solutions_dir: &Path) -> io::Result<Vec<u32>> {
+    let mut days = read_dir(solutions_dir)?
         .flatten()
         .filter(|e| e.path().is_file())
         .flat_map(|e| e.file_name().into_string())
-        .flat_map(|s| s[0..2].parse::<u32>())
+        .flat_map(|s| {
+            s.strip_prefix("day")?
+                .strip_suffix(".rs")?
+                .parse::<u32>()
+                .ok()
+        })
         .collect::<Vec<_>>();
     days.sort_unstable();
     Ok(days)
 }
 
 #[derive(Serialize)]
-struct Context<'a> {
-    days: &'a [u32],
+struct Day {
+    n: u32,
+    /// Rust string literal of the solution file, for its `#[path]` attribute.
+    path: String,
+}
+
+#[derive(Serialize)]
+struct Context {
+    days: Vec<Day>,
 }
 
 fn leading_zero(v: &Value, output: &mut String) -> tinytemplate::error::Result<()> {
@@ -33,41 +46,33 @@ fn leading_zero(v: &Value, output: &mut String) -> tinytemplate::error::Result<(
     Ok(())
 }
 
-fn gen_solutions_mod<P: AsRef<Path>>(p: P, days: &[u32]) -> io::Result<()> {
+fn gen_solutions_mod<P: AsRef<Path>>(p: P, solutions_dir: &Path, days: &[u32]) -> io::Result<()> {
     let mut tpl = TinyTemplate::new();
     tpl.add_template("mod", include_str!("mod.rs.template"))
         .unwrap();
     tpl.add_formatter("leading_zero", leading_zero);
+    let days = days
+        .iter()
+        .map(|&n| Day {
+            n,
+            path: format!("{:?}", solutions_dir.join(format!("day{n:02}.rs"))),
+        })
+        .collect();
     let s = tpl.render("mod", &Context { days }).unwrap();
 
     fs::write(p, s)
 }
 
-fn gen_solutions(dir: &str, days: &[u32]) -> io::Result<()> {
-    for day in days {
-        let file = PathBuf::from(format!("{}/day{:02}.rs", dir, day));
-        if file.exists() {
-            continue;
-        }
-
-        fs::copy("solution.rs.template", file)?;
-    }
-
-    Ok(())
-}
-
 fn main() -> Result<(), Box<dyn Error>> {
-    let input_dir = "./input";
-    let output_dir = "./src/solutions";
-    let solutions_mod_output_path = Path::new(&output_dir).join("mod.rs");
-
-    let days = days(input_dir)?;
+    // the registry is generated out of the sources, then included by `src/solutions.rs`
+    let solutions_dir = Path::new(&env::var("CARGO_MANIFEST_DIR")?).join("src/solutions");
+    let output_path = Path::new(&env::var("OUT_DIR")?).join("solutions.rs");
 
-    // write solutions mod file
-    gen_solutions_mod(solutions_mod_output_path, &days)?;
+    println!("cargo:rerun-if-changed={}", solutions_dir.display());
+    println!("cargo:rerun-if-changed=mod.rs.template");
 
-    // write solutions
-    gen_solutions(output_dir, &days)?;
+    let days = days(&solutions_dir)?;
+    gen_solutions_mod(output_path, &solutions_dir, &days)?;
 
     Ok(())
 }
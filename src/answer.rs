@@ -0,0 +1,127 @@
+use serde::{Serialize, Serializer};
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The answer of a part, whatever the type its solver returns.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Answer {
+    Integer(i64),
+    /// Integers that don't fit in an `i64`.
+    BigInteger(i128),
+    String(String),
+    /// ASCII art, printed below the part.
+    MultiLine(Vec<String>),
+}
+
+impl Answer {
+    pub fn is_multi_line(&self) -> bool {
+        matches!(self, Self::MultiLine(_))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(n) => f.pad(&n.to_string()),
+            Self::BigInteger(n) => f.pad(&n.to_string()),
+            Self::String(s) => f.pad(s),
+            Self::MultiLine(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+/// Integers are numbers unless they are too big for some JSON readers,
+/// multi-line answers are a single string.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Integer(n) => serializer.serialize_i64(*n),
+            other => serializer.collect_str(other),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Self::Integer(n)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        i64::try_from(n).map_or(Self::BigInteger(n.into()), Self::Integer)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Self::from(n as u64)
+    }
+}
+
+/// Integers written as text are read as numbers, the way they are printed back,
+/// so that an answer compares equal whatever the type its solver returns.
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        let canonical = |n: &dyn ToString| n.to_string() == s;
+        match (s.parse::<i64>(), s.parse::<i128>()) {
+            (Ok(n), _) if canonical(&n) => Self::Integer(n),
+            (_, Ok(n)) if canonical(&n) => Self::BigInteger(n),
+            _ if s.contains('\n') => Self::MultiLine(s.lines().map(str::to_string).collect()),
+            _ => Self::String(s),
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::from(s.to_string())
+    }
+}
+
+/// Reads an answer back, e.g. from an examples file, the same way it would have been returned.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Answer::from(6440u64), Answer::Integer(6440));
+        assert_eq!(Answer::from(6440usize), Answer::Integer(6440));
+        assert_eq!(Answer::from(-3i64), Answer::Integer(-3));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInteger(u64::MAX as i128));
+        assert_eq!(
+            Answer::from("#.#\n.#."),
+            Answer::MultiLine(vec!["#.#".to_string(), ".#.".to_string()])
+        );
+
+        for s in ["6440", "-3", "18446744073709551615", "AAA"] {
+            assert_eq!(s.parse::<Answer>().unwrap().to_string(), s);
+        }
+        assert_eq!("6440".parse(), Ok(Answer::from(6440u64)));
+        assert_eq!(Answer::from("6440".to_string()), Answer::Integer(6440));
+        assert_eq!(Answer::from(u64::MAX.to_string()), Answer::from(u64::MAX));
+        assert_eq!(Answer::from("007"), Answer::String("007".to_string()));
+        assert_eq!(Answer::from("+7"), Answer::String("+7".to_string()));
+    }
+
+    #[test]
+    fn formatting() {
+        assert_eq!(format!("{:<6}|", Answer::Integer(42)), "42    |");
+        assert_eq!(format!("{:<6}|", Answer::from("ab")), "ab    |");
+        assert_eq!(Answer::from("#.\n.#").to_string(), "#.\n.#");
+        assert_eq!(
+            serde_json::to_string(&[Answer::Integer(42), Answer::from(u64::MAX)]).unwrap(),
+            r#"[42,"18446744073709551615"]"#
+        );
+    }
+}
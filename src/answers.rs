@@ -0,0 +1,166 @@
+use crate::answer::Answer;
+use anyhow::{anyhow, Context};
+use sha2::{Digest, Sha256};
+use std::collections::hash_map::RandomState;
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::path::Path;
+
+/// Committed with the sources, as it doesn't reveal the answers themselves.
+pub const ANSWERS_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.txt");
+
+/// Salted SHA-256 hashes of the expected answers,
+/// stored one per line as `<day> <part> <salt> <hash> <input>`, the input name last
+/// as it may contain spaces.
+#[derive(Debug, Default)]
+pub struct Answers(BTreeMap<(u32, String, u8), SaltedHash>);
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct SaltedHash {
+    salt: String,
+    hash: String,
+}
+
+impl SaltedHash {
+    fn new(answer: &str) -> Self {
+        let salt = format!("{:016x}", RandomState::new().build_hasher().finish());
+        let hash = Self::hash(&salt, answer);
+        Self { salt, hash }
+    }
+
+    fn hash(salt: &str, answer: &str) -> String {
+        Sha256::new()
+            .chain_update(salt)
+            .chain_update(answer)
+            .finalize()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+
+    fn matches(&self, answer: &str) -> bool {
+        Self::hash(&self.salt, answer) == self.hash
+    }
+}
+
+impl Answers {
+    pub fn load<P: AsRef<Path>>(p: P) -> anyhow::Result<Self> {
+        let p = p.as_ref();
+        let s = fs::read_to_string(p).with_context(|| format!("unable to read {}", p.display()))?;
+
+        s.lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| {
+                let mut it = l.splitn(5, ' ');
+                let mut next = |field| it.next().ok_or(anyhow!("missing {field}"));
+                let day = next("day")?.parse()?;
+                let part = next("part")?.parse()?;
+                let salt = next("salt")?.to_string();
+                let hash = next("hash")?.to_string();
+                let input = next("input")?.to_string();
+                Ok(((day, input, part), SaltedHash { salt, hash }))
+            })
+            .collect::<anyhow::Result<_>>()
+            .map(Self)
+            .with_context(|| format!("invalid answers in {}", p.display()))
+    }
+
+    /// Loads the answers, starting with none if the file doesn't exist yet.
+    pub fn load_or_default<P: AsRef<Path>>(p: P) -> anyhow::Result<Self> {
+        if p.as_ref().exists() {
+            Self::load(p)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, p: P) -> anyhow::Result<()> {
+        let p = p.as_ref();
+        let s = self
+            .0
+            .iter()
+            .map(|((day, input, part), h)| {
+                format!("{day:02} {part} {} {} {input}\n", h.salt, h.hash)
+            })
+            .collect::<String>();
+        fs::write(p, s).with_context(|| format!("unable to write {}", p.display()))
+    }
+
+    /// Whether the answer matches the stored one, `None` if there is no stored answer.
+    pub fn verify(&self, day: u32, input: &str, part: u8, answer: &Answer) -> Option<bool> {
+        self.0
+            .get(&(day, input.to_string(), part))
+            .map(|h| h.matches(&answer.to_string()))
+    }
+
+    /// Stores the answer, keeping the existing entry untouched if it already matches.
+    pub fn record(&mut self, day: u32, input: &str, part: u8, answer: &Answer) {
+        if self.verify(day, input, part, answer) != Some(true) {
+            self.0.insert(
+                (day, input.to_string(), part),
+                SaltedHash::new(&answer.to_string()),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+
+    #[test]
+    fn salted_hash() {
+        let h1 = SaltedHash::new("6440");
+        let h2 = SaltedHash::new("6440");
+        assert_ne!(h1, h2);
+        assert!(h1.matches("6440"));
+        assert!(h2.matches("6440"));
+        assert!(!h1.matches("6441"));
+    }
+
+    #[test]
+    fn record_keeps_matching_entries() {
+        let mut answers = Answers::default();
+        answers.record(7, "07.txt", 1, &Answer::Integer(6440));
+        let h = answers.0.values().next().cloned().unwrap();
+
+        answers.record(7, "07.txt", 1, &Answer::Integer(6440));
+        assert_eq!(answers.0.values().next(), Some(&h));
+
+        answers.record(7, "07.txt", 1, &Answer::Integer(6441));
+        assert_ne!(answers.0.values().next(), Some(&h));
+    }
+
+    #[test]
+    fn save_and_load() {
+        let mut answers = Answers::default();
+        answers.record(7, "07 alice.txt", 2, &Answer::Integer(5905));
+        answers.record(7, "07.txt", 1, &Answer::Integer(6440));
+
+        let p = temp_dir().join("aoc-answers-test.txt");
+        answers.save(&p).unwrap();
+        let s = fs::read_to_string(&p).unwrap();
+        assert!(s.starts_with("07 2 "));
+        assert!(s.lines().any(|l| l.ends_with(" 07 alice.txt")));
+        assert_eq!(s.lines().count(), 2);
+
+        let answers = Answers::load(&p).unwrap();
+        assert_eq!(
+            answers.verify(7, "07.txt", 1, &Answer::Integer(6440)),
+            Some(true)
+        );
+        assert_eq!(
+            answers.verify(7, "07.txt", 1, &Answer::Integer(6441)),
+            Some(false)
+        );
+        assert_eq!(answers.verify(7, "07.txt", 2, &Answer::Integer(5905)), None);
+        assert_eq!(
+            answers.verify(7, "07 alice.txt", 2, &Answer::Integer(5905)),
+            Some(true)
+        );
+
+        fs::remove_file(p).unwrap();
+    }
+}
@@ -0,0 +1,355 @@
+use crate::solver::{DayResult, Parts};
+use crate::table::Table;
+use anyhow::anyhow;
+use humantime::format_duration;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Welch's t above which a slowdown is considered significant, about 97.5%
+/// confidence for the usual number of runs.
+const T_CRITICAL: f64 = 2.0;
+/// Slowdowns below this ratio are ignored even when significant.
+const MIN_SLOWDOWN: f64 = 1.05;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Phase {
+    Parse,
+    Prepare,
+    Part(u8),
+}
+
+impl Phase {
+    /// Short name used in the history file.
+    pub fn key(self) -> String {
+        match self {
+            Self::Parse => "parse".to_string(),
+            Self::Prepare => "prepare".to_string(),
+            Self::Part(n) => n.to_string(),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Self::Parse),
+            "prepare" => Ok(Self::Prepare),
+            "1" | "2" => Ok(Self::Part(s.parse()?)),
+            _ => Err(anyhow!("invalid phase '{s}'")),
+        }
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse => write!(f, "Parsing"),
+            Self::Prepare => write!(f, "Preparing"),
+            Self::Part(n) => write!(f, "Part {n}"),
+        }
+    }
+}
+
+/// Summary of the timings of a phase over several runs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// `None` without samples. The standard deviation is the sample one.
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let min = *sorted.first()?;
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let (_, variance) = mean_variance(&sorted);
+
+        Some(Self {
+            min,
+            median,
+            mean: sorted.iter().sum::<Duration>() / n as u32,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Mean and sample variance in seconds.
+fn mean_variance(samples: &[Duration]) -> (f64, f64) {
+    let n = samples.len() as f64;
+    let mean = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / n;
+    let variance = samples
+        .iter()
+        .map(|t| (t.as_secs_f64() - mean).powi(2))
+        .sum::<f64>()
+        / (n - 1.0).max(1.0);
+    (mean, variance)
+}
+
+/// How the timings of a phase changed since a baseline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Change {
+    /// Ratio of the means, above 1 when slower.
+    pub ratio: f64,
+    /// Welch's t statistic, positive when slower.
+    pub t: f64,
+}
+
+impl Change {
+    /// `None` if either side has no samples.
+    pub fn new(baseline: &[Duration], samples: &[Duration]) -> Option<Self> {
+        if baseline.is_empty() || samples.is_empty() {
+            return None;
+        }
+
+        let (m1, v1) = mean_variance(baseline);
+        let (m2, v2) = mean_variance(samples);
+        let error = (v1 / baseline.len() as f64 + v2 / samples.len() as f64).sqrt();
+        let t = if error > 0.0 {
+            (m2 - m1) / error
+        } else {
+            // no variance at all, any difference is significant
+            (m2 - m1).signum() * f64::INFINITY
+        };
+
+        Some(Self { ratio: m2 / m1, t })
+    }
+
+    pub fn is_regression(&self) -> bool {
+        self.t > T_CRITICAL && self.ratio > MIN_SLOWDOWN
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:+.1}%", (self.ratio - 1.0) * 100.0)
+    }
+}
+
+/// Timings of every phase of a day, parts failing in any run keeping their failure.
+#[derive(Debug, Default)]
+pub struct Samples {
+    pub parse: Vec<Duration>,
+    pub prepare: Vec<Duration>,
+    pub parts: [Vec<Duration>; 2],
+    pub failures: [Option<String>; 2],
+}
+
+impl Samples {
+    /// The phases that succeeded in every run with their timings.
+    pub fn phases(&self) -> impl Iterator<Item = (Phase, &[Duration])> {
+        let parts = (0..2)
+            .filter(|&i| self.failures[i].is_none())
+            .map(|i| (Phase::Part(i as u8 + 1), self.parts[i].as_slice()));
+        [
+            (Phase::Parse, self.parse.as_slice()),
+            (Phase::Prepare, self.prepare.as_slice()),
+        ]
+        .into_iter()
+        .chain(parts)
+        .filter(|(_, samples)| !samples.is_empty())
+    }
+
+    pub fn push(&mut self, r: &DayResult) {
+        self.parse.push(r.parse_time);
+        self.prepare.extend(r.prepare_time);
+        for (n, p) in r.parts() {
+            if p.answer.is_ok() {
+                self.parts[n as usize - 1].push(p.time);
+            }
+        }
+        self.push_failures(r);
+    }
+
+    /// Keeps the failures of a run without its timings, e.g. of a warmup run.
+    pub fn push_failures(&mut self, r: &DayResult) {
+        for (n, p) in r.parts() {
+            if p.answer.is_err() {
+                self.failures[n as usize - 1].get_or_insert_with(|| p.summary());
+            }
+        }
+    }
+
+    /// The parts left to solve in the next runs, `None` if all of them failed.
+    /// Failed parts aren't solved again, as a timed out part keeps running on its
+    /// own thread and would slow down every later run.
+    pub fn pending(&self, parts: Parts) -> Option<Parts> {
+        let pending = |part: u8| parts.includes(part) && self.failures[part as usize - 1].is_none();
+        match (pending(1), pending(2)) {
+            (true, true) => Some(Parts::Both),
+            (true, false) => Some(Parts::Only(1)),
+            (false, true) => Some(Parts::Only(2)),
+            (false, false) => None,
+        }
+    }
+
+    pub fn table(&self) -> Table {
+        let mut table = Table::new(["Phase", "Min", "Median", "Mean", "Stddev"]);
+        let phases = [
+            (Phase::Parse, &self.parse, &None),
+            (Phase::Prepare, &self.prepare, &None),
+            (Phase::Part(1), &self.parts[0], &self.failures[0]),
+            (Phase::Part(2), &self.parts[1], &self.failures[1]),
+        ];
+
+        for (phase, samples, failure) in phases {
+            if let Some(failure) = failure {
+                table.push(vec![phase.to_string(), failure.clone()]);
+            } else if let Some(s) = Stats::new(samples) {
+                let mut row = vec![phase.to_string()];
+                row.extend(
+                    [s.min, s.median, s.mean, s.stddev]
+                        .map(|t| format_duration(round(t)).to_string()),
+                );
+                table.push(row);
+            }
+        }
+
+        table
+    }
+}
+
+/// Rounds to the microsecond above a millisecond so that columns stay readable.
+pub fn round(t: Duration) -> Duration {
+    if t < Duration::from_millis(1) {
+        t
+    } else {
+        Duration::from_micros(t.as_micros() as u64)
+    }
+}
+
+/// Powers of two up to `max`, followed by `max` itself.
+pub fn thread_counts(max: usize) -> Vec<usize> {
+    let mut counts = std::iter::successors(Some(1), |n| Some(n * 2))
+        .take_while(|&n| n < max)
+        .collect::<Vec<_>>();
+    counts.push(max);
+    counts
+}
+
+/// CPU time used so far by all the threads of the process, `None` outside of Linux.
+#[cfg(target_os = "linux")]
+pub fn process_cpu_time() -> Option<Duration> {
+    let mut t = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: the clock only writes to the given timespec
+    let r = unsafe { libc::clock_gettime(libc::CLOCK_PROCESS_CPUTIME_ID, &mut t) };
+    (r == 0).then(|| Duration::new(t.tv_sec as u64, t.tv_nsec as u32))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn process_cpu_time() -> Option<Duration> {
+    None
+}
+
+/// Pins the current thread, and the threads it spawns from now on, to a single CPU.
+#[cfg(target_os = "linux")]
+pub fn pin_to_cpu(cpu: usize) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    if cpu >= libc::CPU_SETSIZE as usize {
+        anyhow::bail!("CPU {cpu} is out of range");
+    }
+
+    // SAFETY: the set is a plain bitmask, zeroed then filled in place
+    let r = unsafe {
+        let mut set = std::mem::zeroed::<libc::cpu_set_t>();
+        libc::CPU_SET(cpu, &mut set);
+        libc::sched_setaffinity(0, size_of::<libc::cpu_set_t>(), &set)
+    };
+
+    if r != 0 {
+        return Err(std::io::Error::last_os_error())
+            .with_context(|| format!("unable to pin to CPU {cpu}"));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_to_cpu(_cpu: usize) -> anyhow::Result<()> {
+    anyhow::bail!("pinning to a CPU is only supported on Linux")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        assert_eq!(Stats::new(&[]), None);
+
+        let s = Stats::new(&[ms(4), ms(2), ms(6), ms(8)]).unwrap();
+        assert_eq!(s.min, ms(2));
+        assert_eq!(s.median, ms(5));
+        assert_eq!(s.mean, ms(5));
+        assert_eq!(s.stddev.as_micros(), 2581);
+
+        let s = Stats::new(&[ms(3)]).unwrap();
+        assert_eq!((s.median, s.mean, s.stddev), (ms(3), ms(3), Duration::ZERO));
+    }
+
+    #[test]
+    fn changes() {
+        let ms = Duration::from_millis;
+        let baseline = [ms(10), ms(11), ms(9), ms(10)];
+
+        let noisy = Change::new(&baseline, &[ms(9), ms(13), ms(10), ms(12)]).unwrap();
+        assert!(noisy.ratio > 1.05);
+        assert!(!noisy.is_regression());
+
+        let slower = Change::new(&baseline, &[ms(13), ms(12), ms(14), ms(13)]).unwrap();
+        assert_eq!(slower.to_string(), "+30.0%");
+        assert!(slower.is_regression());
+
+        let faster = Change::new(&baseline, &[ms(5), ms(6), ms(5), ms(6)]).unwrap();
+        assert!(faster.t < 0.0);
+        assert!(!faster.is_regression());
+
+        assert_eq!(Change::new(&[], &baseline), None);
+    }
+
+    #[test]
+    fn pending_parts() {
+        let mut samples = Samples::default();
+        assert_eq!(samples.pending(Parts::Both), Some(Parts::Both));
+        assert_eq!(samples.pending(Parts::Only(2)), Some(Parts::Only(2)));
+
+        samples.failures[0] = Some("timed out after 1s".to_string());
+        assert_eq!(samples.pending(Parts::Both), Some(Parts::Only(2)));
+        assert_eq!(samples.pending(Parts::Only(1)), None);
+
+        samples.failures[1] = Some("unsolved".to_string());
+        assert_eq!(samples.pending(Parts::Both), None);
+    }
+
+    #[test]
+    fn thread_count_steps() {
+        assert_eq!(thread_counts(1), [1]);
+        assert_eq!(thread_counts(4), [1, 2, 4]);
+        assert_eq!(thread_counts(12), [1, 2, 4, 8, 12]);
+    }
+
+    #[test]
+    fn phase_keys() {
+        for phase in [Phase::Parse, Phase::Prepare, Phase::Part(1), Phase::Part(2)] {
+            assert_eq!(phase.key().parse::<Phase>().unwrap(), phase);
+        }
+        assert!("3".parse::<Phase>().is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::output::Format;
+use crate::params::Param;
+use crate::selection::DaySelection;
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(version, about = "Advent of Code 2023 solutions")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Solve days and print their answers with timings
+    Run {
+        #[command(flatten)]
+        days: DayArgs,
+        /// Output format, json and csv print one record per part and input
+        #[arg(short, long, value_enum, default_value_t)]
+        format: Format,
+        /// Solve the days in parallel, timings then include the contention between days
+        #[arg(long)]
+        parallel: bool,
+    },
+    /// List solved days and whether their input is present
+    List,
+    /// Create the solution of a day from the template, with an empty example
+    New {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+    /// Verify answers against the hashes stored in answers.txt,
+    /// or against the expected ones with --examples
+    Check {
+        #[command(flatten)]
+        days: DayArgs,
+        /// Record the current answers instead of comparing them
+        #[arg(long)]
+        record: bool,
+    },
+    /// Solve days and print a Markdown table of their status and timings
+    Report {
+        #[command(flatten)]
+        days: DayArgs,
+        /// Write the table between the markers of README.md instead of printing it
+        #[arg(long)]
+        markdown: bool,
+    },
+    /// Time the main input of days with 1, 2, 4, … threads and print the speedups
+    Scaling {
+        #[command(flatten)]
+        days: DayArgs,
+        #[command(flatten)]
+        options: ScalingArgs,
+    },
+    /// Time solutions over several runs and print statistics
+    Bench {
+        #[command(flatten)]
+        days: DayArgs,
+        #[command(flatten)]
+        options: BenchArgs,
+    },
+}
+
+#[derive(Args)]
+pub struct BenchArgs {
+    /// Number of timed runs per input
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub iterations: u32,
+    /// Number of untimed runs per input before the timed ones
+    #[arg(short, long, default_value_t = 3)]
+    pub warmup: u32,
+    /// Pin the benchmark to this CPU (Linux only)
+    #[arg(long, value_name = "CPU")]
+    pub pin: Option<usize>,
+    /// Flag phases significantly slower than their latest stored timings,
+    /// or than the ones of a revision
+    #[arg(long, value_name = "REV", num_args = 0..=1, require_equals = true)]
+    pub compare: Option<Option<String>>,
+}
+
+#[derive(Args)]
+pub struct ScalingArgs {
+    /// Number of timed runs per thread count
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 5,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub iterations: u32,
+    /// Largest number of threads, the number of CPUs by default
+    #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub max_threads: Option<usize>,
+}
+
+#[derive(Args, Default)]
+pub struct DayArgs {
+    /// Days to solve: a day, a range like 3-7, a list like 1,4,9, `latest` or `all`
+    #[arg(default_value = "all")]
+    pub days: DaySelection,
+    /// Only solve this part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+    /// Input file to use instead of the day's one, `-` for the standard input
+    #[arg(short, long, value_name = "PATH")]
+    pub input: Option<PathBuf>,
+    /// Solve the examples from the puzzle statements instead of the inputs
+    #[arg(short, long, conflicts_with = "input")]
+    pub examples: bool,
+    /// Time budget of each part, e.g. `30s` or `2m`
+    #[arg(short, long, value_name = "DURATION")]
+    pub timeout: Option<humantime::Duration>,
+    /// Number of threads used by the solutions, the number of CPUs by default
+    #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub threads: Option<usize>,
+    /// Puzzle constant to use instead of the actual one, e.g. `expand=10`
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    pub params: Vec<Param>,
+}
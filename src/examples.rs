@@ -0,0 +1,149 @@
+use crate::answer::Answer;
+use crate::input::{day_files, InputSource};
+use crate::solver::Parts;
+use anyhow::{anyhow, bail, Context};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+pub const EXAMPLES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples");
+
+/// Opens and closes multi-line answers in answers files.
+const BLOCK: &str = "\"\"\"";
+
+/// An example from a puzzle statement, `NN-k.txt` in the examples directory,
+/// with its expected answers in `NN-k.answers`, one `<part> <answer>` per line.
+/// Multi-line answers go on the lines between `<part> \"\"\"` and `\"\"\"`.
+#[derive(Clone, Debug)]
+pub struct Example {
+    pub source: InputSource,
+    pub expected: BTreeMap<u8, Answer>,
+}
+
+impl Example {
+    /// Every example of a day, in name order.
+    pub fn all_for_day(day: u32) -> anyhow::Result<Vec<Self>> {
+        day_files(EXAMPLES_DIR, day)
+            .into_iter()
+            .map(Self::load)
+            .collect()
+    }
+
+    fn load(p: PathBuf) -> anyhow::Result<Self> {
+        let answers = p.with_extension("answers");
+        let expected = if answers.exists() {
+            let s = fs::read_to_string(&answers)
+                .with_context(|| format!("unable to read {}", answers.display()))?;
+            parse_answers(&s)
+                .with_context(|| format!("invalid answers in {}", answers.display()))?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self {
+            source: InputSource::File(p),
+            expected,
+        })
+    }
+
+    /// The parts the example has answers for, most only apply to one part.
+    pub fn parts(&self) -> Parts {
+        match self.expected.keys().collect::<Vec<_>>().as_slice() {
+            [&p] => Parts::Only(p),
+            _ => Parts::Both,
+        }
+    }
+}
+
+fn parse_answers(s: &str) -> anyhow::Result<BTreeMap<u8, Answer>> {
+    let mut answers = BTreeMap::new();
+    let mut lines = s.lines();
+
+    while let Some(l) = lines.next() {
+        if l.trim().is_empty() {
+            continue;
+        }
+        let (part, answer) = l.split_once(' ').ok_or(anyhow!("missing answer"))?;
+        let answer = match answer.trim() {
+            BLOCK => {
+                let mut block = vec![];
+                loop {
+                    match lines.next() {
+                        Some(BLOCK) => break,
+                        Some(l) => block.push(l),
+                        None => bail!("unterminated answer of part {part}"),
+                    }
+                }
+                Answer::from(block.join("\n"))
+            }
+            answer => answer.parse()?,
+        };
+        answers.insert(part.parse()?, answer);
+    }
+
+    Ok(answers)
+}
+
+/// Solves every example of a day and compares the answers with the expected ones.
+#[cfg(test)]
+pub fn check_examples(day: u32) {
+    use crate::solver::{solve, solver_for, SolveOptions};
+
+    // examples without answers yet have nothing to check
+    for example in Example::all_for_day(day)
+        .unwrap()
+        .into_iter()
+        .filter(|e| !e.expected.is_empty())
+    {
+        let name = example.source.name();
+        let options = SolveOptions {
+            parts: example.parts(),
+            ..Default::default()
+        };
+        let r = solve(solver_for(day).unwrap(), &example.source, &[], options)
+            .unwrap_or_else(|e| panic!("{name}: {e:#}"));
+
+        for (part, expected) in &example.expected {
+            let (_, p) = r
+                .parts()
+                .find(|&(p, _)| p == *part)
+                .unwrap_or_else(|| panic!("{name}: part {part} wasn't solved"));
+            assert_eq!(
+                p.answer.as_ref().ok(),
+                Some(expected),
+                "{name}, part {part}: {}",
+                p.summary()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parts() {
+        let mut example = Example {
+            source: InputSource::Stdin,
+            expected: BTreeMap::new(),
+        };
+        assert_eq!(example.parts(), Parts::Both);
+
+        example.expected.insert(2, Answer::Integer(281));
+        assert_eq!(example.parts(), Parts::Only(2));
+
+        example.expected.insert(1, Answer::Integer(142));
+        assert_eq!(example.parts(), Parts::Both);
+    }
+
+    #[test]
+    fn answers() {
+        let answers = parse_answers("1 142\n\n2 \"\"\"\n#..#\n.##.\n\"\"\"\n").unwrap();
+        assert_eq!(answers[&1], Answer::Integer(142));
+        assert_eq!(answers[&2], Answer::from("#..#\n.##."));
+
+        assert!(parse_answers("1").is_err());
+        assert!(parse_answers("2 \"\"\"\n#..#\n").is_err());
+    }
+}
@@ -2,7 +2,6 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt::{Display, Error, Formatter};
 use std::io::{BufRead, BufReader, Read};
-use std::iter::{repeat, FromIterator};
 use std::str::FromStr;
 
 #[derive(Clone, Debug)]
@@ -26,7 +25,7 @@ impl<T> Grid<T> {
         T: Clone,
     {
         Self {
-            cells: Vec::from_iter(repeat(val).take(w * h)),
+            cells: vec![val; w * h],
             w,
             h,
         }
@@ -257,6 +256,7 @@ where
 pub trait Coord {
     fn x(&self) -> usize;
     fn y(&self) -> usize;
+    #[allow(dead_code)]
     fn coords(&self) -> (usize, usize) {
         (self.x(), self.y())
     }
@@ -0,0 +1,271 @@
+use crate::bench::Phase;
+use crate::input::InputSource;
+use crate::params::Param;
+use anyhow::{anyhow, bail, Context};
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Local to each machine, timings from different ones can't be compared.
+pub const HISTORY_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/bench-history.txt");
+
+/// Settings of runs made without any of the options changing timings.
+pub const DEFAULT_SETTINGS: &str = "default";
+
+/// Timings of a phase in one benchmark, stored one per line as
+/// `<day> <phase> <input hash> <revision> <settings> <ns>,<ns>,...`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    pub day: u32,
+    pub phase: Phase,
+    pub input: String,
+    pub revision: String,
+    /// Options the timings depend on, see [`settings`].
+    pub settings: String,
+    pub samples: Vec<Duration>,
+}
+
+impl Entry {
+    fn parse(l: &str) -> anyhow::Result<Self> {
+        let mut it = l.split(' ');
+        let mut next = |field| it.next().ok_or(anyhow!("missing {field}"));
+        let day = next("day")?.parse()?;
+        let phase = next("phase")?.parse()?;
+        let input = next("input hash")?.to_string();
+        let revision = next("revision")?.to_string();
+        let settings = next("settings")?.to_string();
+        let samples = next("samples")?
+            .split(',')
+            .map(|ns| Ok(Duration::from_nanos(ns.parse()?)))
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self {
+            day,
+            phase,
+            input,
+            revision,
+            settings,
+            samples,
+        })
+    }
+}
+
+/// Describes the options changing timings, such as running on a single CPU or
+/// with puzzle parameters, e.g. `pin=0,threads=4,expand=10`. Only timings with
+/// the same settings are compared.
+pub fn settings(
+    pin: Option<usize>,
+    threads: Option<usize>,
+    params: &[Param],
+) -> anyhow::Result<String> {
+    let mut items = [
+        pin.map(|cpu| format!("pin={cpu}")),
+        threads.map(|n| format!("threads={n}")),
+        cfg!(feature = "count-allocs").then(|| "count-allocs".to_string()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    for p in params {
+        let p = p.to_string();
+        if p.contains(|c: char| c == ',' || c.is_whitespace()) {
+            bail!("parameter '{p}' can't be stored in the history, it has a comma or a space");
+        }
+        items.push(p);
+    }
+
+    Ok(if items.is_empty() {
+        DEFAULT_SETTINGS.to_string()
+    } else {
+        items.join(",")
+    })
+}
+
+/// Every benchmark ran so far, oldest first.
+#[derive(Debug, Default)]
+pub struct History(Vec<Entry>);
+
+impl History {
+    /// Loads the history, starting with none if the file doesn't exist yet.
+    pub fn load_or_default<P: AsRef<Path>>(p: P) -> anyhow::Result<Self> {
+        let p = p.as_ref();
+        if !p.exists() {
+            return Ok(Self::default());
+        }
+        let s = fs::read_to_string(p).with_context(|| format!("unable to read {}", p.display()))?;
+
+        s.lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(Entry::parse)
+            .collect::<anyhow::Result<_>>()
+            .map(Self)
+            .with_context(|| format!("invalid history in {}", p.display()))
+    }
+
+    /// Appends entries to the file, keeping the previous ones.
+    pub fn append<P: AsRef<Path>>(p: P, entries: &[Entry]) -> anyhow::Result<()> {
+        let p = p.as_ref();
+        let s = entries
+            .iter()
+            .map(|e| {
+                let samples = e
+                    .samples
+                    .iter()
+                    .map(|t| t.as_nanos().to_string())
+                    .collect::<Vec<_>>();
+                format!(
+                    "{:02} {} {} {} {} {}\n",
+                    e.day,
+                    e.phase.key(),
+                    e.input,
+                    e.revision,
+                    e.settings,
+                    samples.join(",")
+                )
+            })
+            .collect::<String>();
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(p)
+            .and_then(|mut f| f.write_all(s.as_bytes()))
+            .with_context(|| format!("unable to write {}", p.display()))
+    }
+
+    /// The latest timings of the same phase on the same input with the same settings,
+    /// only considering the revisions starting with `revision` if given.
+    pub fn baseline(&self, entry: &Entry, revision: Option<&str>) -> Option<&Entry> {
+        self.0.iter().rev().find(|e| {
+            e.day == entry.day
+                && e.phase == entry.phase
+                && e.input == entry.input
+                && e.settings == entry.settings
+                && revision.is_none_or(|r| e.revision.starts_with(r))
+        })
+    }
+}
+
+/// Short hash of the contents of an input, so that timings are only compared on the same data.
+pub fn input_hash(source: &InputSource) -> anyhow::Result<String> {
+    let mut data = vec![];
+    source
+        .open()
+        .and_then(|mut r| r.read_to_end(&mut data))
+        .with_context(|| format!("unable to read {source}"))?;
+
+    Ok(Sha256::digest(&data)[..8]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+/// Current commit, read from `.git` without running git, `unknown` outside of a repository.
+pub fn git_revision() -> String {
+    let git = Path::new(env!("CARGO_MANIFEST_DIR")).join(".git");
+    let resolve = || -> Option<String> {
+        let head = fs::read_to_string(git.join("HEAD")).ok()?;
+        let Some(name) = head.trim().strip_prefix("ref: ") else {
+            return Some(head.trim().to_string());
+        };
+        if let Ok(hash) = fs::read_to_string(git.join(name)) {
+            return Some(hash.trim().to_string());
+        }
+        // refs may have been packed by git gc
+        fs::read_to_string(git.join("packed-refs"))
+            .ok()?
+            .lines()
+            .find_map(|l| Some(l.strip_suffix(name)?.strip_suffix(' ')?.to_string()))
+    };
+
+    resolve().map_or_else(
+        || "unknown".to_string(),
+        |hash| hash.chars().take(12).collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+
+    fn entry(phase: Phase, revision: &str, ms: u64) -> Entry {
+        Entry {
+            day: 5,
+            phase,
+            input: "0123456789abcdef".to_string(),
+            revision: revision.to_string(),
+            settings: DEFAULT_SETTINGS.to_string(),
+            samples: vec![Duration::from_millis(ms), Duration::from_nanos(1500)],
+        }
+    }
+
+    #[test]
+    fn append_and_load() {
+        let p = temp_dir().join("aoc-history-test.txt");
+        let _ = fs::remove_file(&p);
+
+        History::append(&p, &[entry(Phase::Parse, "aaaa", 1)]).unwrap();
+        History::append(&p, &[entry(Phase::Part(2), "bbbb", 2)]).unwrap();
+        let s = fs::read_to_string(&p).unwrap();
+        assert_eq!(
+            s.lines().last(),
+            Some("05 2 0123456789abcdef bbbb default 2000000,1500")
+        );
+
+        let history = History::load_or_default(&p).unwrap();
+        assert_eq!(
+            history.0,
+            [
+                entry(Phase::Parse, "aaaa", 1),
+                entry(Phase::Part(2), "bbbb", 2)
+            ]
+        );
+
+        fs::remove_file(p).unwrap();
+    }
+
+    #[test]
+    fn baseline() {
+        let history = History(vec![
+            entry(Phase::Part(1), "aaaa", 1),
+            entry(Phase::Part(1), "bbbb", 2),
+            entry(Phase::Part(2), "cccc", 3),
+        ]);
+        let current = entry(Phase::Part(1), "dddd", 4);
+
+        assert_eq!(history.baseline(&current, None).unwrap().revision, "bbbb");
+        assert_eq!(
+            history.baseline(&current, Some("aa")).unwrap().revision,
+            "aaaa"
+        );
+        assert_eq!(history.baseline(&current, Some("cc")), None);
+
+        let pinned = Entry {
+            settings: "pin=0".to_string(),
+            ..current
+        };
+        assert_eq!(history.baseline(&pinned, None), None);
+    }
+
+    #[test]
+    fn run_settings() {
+        let expand = "expand=10".parse::<Param>().unwrap();
+        let s = settings(Some(0), None, &[expand]).unwrap();
+        if cfg!(feature = "count-allocs") {
+            assert_eq!(s, "pin=0,count-allocs,expand=10");
+        } else {
+            assert_eq!(s, "pin=0,expand=10");
+            assert_eq!(settings(None, None, &[]).unwrap(), DEFAULT_SETTINGS);
+        }
+        assert!(settings(None, None, &["start=A A".parse().unwrap()]).is_err());
+    }
+
+    #[test]
+    fn revision() {
+        let revision = git_revision();
+        assert!(revision == "unknown" || revision.len() == 12);
+    }
+}
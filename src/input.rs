@@ -0,0 +1,115 @@
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs::{read_dir, File};
+use std::io;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+const DEFAULT_INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+
+/// Directory containing the puzzle inputs, `AOC_INPUT_DIR` or `input` in the crate
+/// by default, wherever the command runs from.
+pub fn input_dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// The default input of a day, `NN.txt` in the input directory.
+    pub fn for_day(day: u32) -> Self {
+        Self::File(input_dir().join(format!("{:02}.txt", day)))
+    }
+
+    /// Every input of a day: `NN.txt` first, then variants such as `NN-alice.txt`.
+    pub fn all_for_day(day: u32) -> Vec<Self> {
+        day_files(input_dir(), day)
+            .into_iter()
+            .map(Self::File)
+            .collect()
+    }
+
+    /// Parses a command line argument, `-` meaning the standard input.
+    pub fn from_arg<P: AsRef<Path>>(p: P) -> Self {
+        let p = p.as_ref();
+        if p == Path::new("-") {
+            Self::Stdin
+        } else {
+            Self::File(p.to_path_buf())
+        }
+    }
+
+    /// Short name used in tables, the file name without its directory.
+    pub fn name(&self) -> String {
+        match self {
+            Self::File(p) => p
+                .file_name()
+                .map_or_else(|| p.display().to_string(), |n| n.to_string_lossy().into()),
+            Self::Stdin => self.to_string(),
+        }
+    }
+
+    /// Opens the input for reading. The standard input is buffered on first use
+    /// so that it can be read again, e.g. when benchmarking.
+    pub fn open(&self) -> io::Result<Box<dyn Read>> {
+        match self {
+            Self::File(p) => Ok(Box::new(File::open(p)?)),
+            Self::Stdin => Ok(Box::new(stdin()?)),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(p) => write!(f, "{}", p.display()),
+            Self::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// Files of a directory named after a day: `NN.txt` first, then `NN*.txt` in name order.
+pub fn day_files<P: AsRef<Path>>(dir: P, day: u32) -> Vec<PathBuf> {
+    let prefix = format!("{:02}", day);
+    let default = format!("{prefix}.txt");
+    let Ok(entries) = read_dir(&dir) else {
+        return vec![];
+    };
+
+    let mut names = entries
+        .flatten()
+        .filter(|e| e.path().is_file())
+        .flat_map(|e| e.file_name().into_string())
+        .filter(|name| {
+            name.strip_prefix(&prefix)
+                .and_then(|s| s.strip_suffix(".txt"))
+                .is_some_and(|s| !s.starts_with(|c: char| c.is_ascii_digit()))
+        })
+        .collect::<Vec<_>>();
+    names.sort_unstable_by_key(|name| (*name != default, name.clone()));
+
+    names
+        .into_iter()
+        .map(|name| dir.as_ref().join(name))
+        .collect()
+}
+
+fn stdin() -> io::Result<&'static [u8]> {
+    static BUFFER: OnceLock<Vec<u8>> = OnceLock::new();
+
+    if let Some(b) = BUFFER.get() {
+        return Ok(b);
+    }
+
+    let mut buf = vec![];
+    io::stdin().read_to_end(&mut buf)?;
+    Ok(BUFFER.get_or_init(|| buf))
+}
@@ -1,15 +1,53 @@
-use crate::solutions::{exec_all_days, exec_day};
-use std::env;
+use crate::cli::{Cli, Command};
+use clap::Parser;
 
+mod alloc;
+mod answer;
+mod answers;
+mod bench;
+mod cli;
+mod examples;
 mod grid;
+mod history;
+mod input;
+mod memory;
+mod output;
+mod params;
 mod parsing;
+mod report;
+mod runner;
+mod scaffold;
+mod selection;
 mod solutions;
 mod solver;
+mod table;
 
-fn main() {
-    if let Some(day) = env::args().nth(1) {
-        exec_day(day.parse().unwrap_or(1));
-    } else {
-        exec_all_days();
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static ALLOCATOR: alloc::CountingAlloc = alloc::CountingAlloc;
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let command = cli.command.unwrap_or(Command::Run {
+        days: Default::default(),
+        format: Default::default(),
+        parallel: false,
+    });
+
+    match command {
+        Command::Run {
+            days,
+            format,
+            parallel,
+        } => runner::run(&days, format, parallel)?,
+        Command::List => runner::list(),
+        Command::New { day } => runner::new(day)?,
+        Command::Check { days, record } => runner::check(&days, record)?,
+        Command::Report { days, markdown } => runner::report(&days, markdown)?,
+        Command::Scaling { days, options } => runner::scaling(&days, &options)?,
+        Command::Bench { days, options } => runner::bench(&days, &options)?,
     }
+
+    Ok(())
 }
@@ -0,0 +1,38 @@
+use std::fs;
+
+/// Peak resident set size of the process in bytes, `VmHWM` in `/proc/self/status`.
+/// `None` where it isn't available, i.e. outside of Linux.
+pub fn peak_rss() -> Option<usize> {
+    parse_vm_hwm(&fs::read_to_string("/proc/self/status").ok()?)
+}
+
+/// Resets the peak resident set size to the current one, so that the next reading
+/// only covers what runs from now on. Returns whether the kernel allowed it.
+pub fn reset_peak_rss() -> bool {
+    fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+fn parse_vm_hwm(status: &str) -> Option<usize> {
+    let kb = status
+        .lines()
+        .find_map(|l| l.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<usize>()
+        .ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vm_hwm() {
+        let status =
+            "Name:\taoc-rs-2023\nVmPeak:\t  12000 kB\nVmHWM:\t    3412 kB\nVmRSS:\t    3000 kB\n";
+        assert_eq!(parse_vm_hwm(status), Some(3412 * 1024));
+        assert_eq!(parse_vm_hwm("VmRSS:\t 3000 kB\n"), None);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::answer::Answer;
+use crate::input::InputSource;
+use crate::solver::{DayResult, Parts};
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Aligned text for humans
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+    /// Comma separated values with a header
+    Csv,
+}
+
+/// The outcome of a part on an input, timings being in nanoseconds.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub day: u32,
+    pub input: String,
+    pub part: u8,
+    pub status: &'static str,
+    pub answer: Option<Answer>,
+    /// Error or panic message when the part failed.
+    pub message: Option<String>,
+    pub parse_ns: Option<u128>,
+    /// Only set when the solver shares work between parts.
+    pub prepare_ns: Option<u128>,
+    pub time_ns: Option<u128>,
+    /// Peak RSS of the day, the same for all its records, `None` when it couldn't
+    /// be measured on its own, outside of Linux or when solving days in parallel.
+    pub rss_bytes: Option<usize>,
+}
+
+impl Record {
+    const CSV_HEADER: &'static str =
+        "day,input,part,status,answer,message,parse_ns,prepare_ns,time_ns,rss_bytes";
+
+    /// One record per solved part, or per selected part when the parsing failed.
+    pub fn all(
+        day: u32,
+        source: &InputSource,
+        parts: Parts,
+        result: &anyhow::Result<DayResult>,
+        rss_bytes: Option<usize>,
+    ) -> Vec<Self> {
+        let input = source.name();
+
+        match result {
+            Ok(r) => r
+                .parts()
+                .map(|(part, p)| Self {
+                    day,
+                    input: input.clone(),
+                    part,
+                    status: p.status(),
+                    answer: p.answer.as_ref().ok().cloned(),
+                    message: p.answer.as_ref().err().map(|e| format!("{:#}", e)),
+                    parse_ns: Some(r.parse_time.as_nanos()),
+                    prepare_ns: r.prepare_time.map(|t| t.as_nanos()),
+                    time_ns: Some(p.time.as_nanos()),
+                    rss_bytes,
+                })
+                .collect(),
+            Err(e) => [1, 2]
+                .into_iter()
+                .filter(|&part| parts.includes(part))
+                .map(|part| Self {
+                    day,
+                    input: input.clone(),
+                    part,
+                    status: "error",
+                    answer: None,
+                    message: Some(format!("{:#}", e)),
+                    parse_ns: None,
+                    prepare_ns: None,
+                    time_ns: None,
+                    rss_bytes,
+                })
+                .collect(),
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let optional = |v: Option<u128>| v.map(|v| v.to_string()).unwrap_or_default();
+        let rss = self.rss_bytes.map(|v| v.to_string()).unwrap_or_default();
+        [
+            self.day.to_string(),
+            csv_field(&self.input),
+            self.part.to_string(),
+            self.status.to_string(),
+            csv_field(
+                &self
+                    .answer
+                    .as_ref()
+                    .map(Answer::to_string)
+                    .unwrap_or_default(),
+            ),
+            csv_field(self.message.as_deref().unwrap_or_default()),
+            optional(self.parse_ns),
+            optional(self.prepare_ns),
+            optional(self.time_ns),
+            rss,
+        ]
+        .join(",")
+    }
+}
+
+impl Format {
+    /// Line to print before the records, if any.
+    pub fn header(self) -> Option<&'static str> {
+        (self == Self::Csv).then_some(Record::CSV_HEADER)
+    }
+
+    /// Renders a record on a single line, `None` for the text format.
+    pub fn record(self, record: &Record) -> Option<String> {
+        match self {
+            Self::Text => None,
+            Self::Json => serde_json::to_string(record).ok(),
+            Self::Csv => Some(record.to_csv()),
+        }
+    }
+}
+
+/// Quotes a field if needed, answers may span several lines.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{Abort, PartResult};
+    use anyhow::anyhow;
+    use std::time::Duration;
+
+    fn records() -> Vec<Record> {
+        let result = DayResult {
+            parse_time: Duration::from_nanos(1200),
+            parse_allocs: None,
+            prepare_time: Some(Duration::from_nanos(400)),
+            prepare_allocs: None,
+            first: Some(PartResult {
+                answer: Ok(Answer::Integer(6440)),
+                time: Duration::from_nanos(300),
+                allocs: None,
+            }),
+            second: Some(PartResult {
+                answer: Err(Abort::Panicked("bad, \"really\"".to_string()).into()),
+                time: Duration::from_nanos(10),
+                allocs: None,
+            }),
+        };
+        Record::all(7, &InputSource::Stdin, Parts::Both, &Ok(result), Some(4096))
+    }
+
+    #[test]
+    fn json() {
+        let lines = records()
+            .iter()
+            .map(|r| Format::Json.record(r).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                r#"{"day":7,"input":"<stdin>","part":1,"status":"solved","answer":6440,"message":null,"parse_ns":1200,"prepare_ns":400,"time_ns":300,"rss_bytes":4096}"#,
+                r#"{"day":7,"input":"<stdin>","part":2,"status":"panicked","answer":null,"message":"panicked: bad, \"really\"","parse_ns":1200,"prepare_ns":400,"time_ns":10,"rss_bytes":4096}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn csv() {
+        let lines = records()
+            .iter()
+            .map(|r| Format::Csv.record(r).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "7,<stdin>,1,solved,6440,,1200,400,300,4096",
+                r#"7,<stdin>,2,panicked,,"panicked: bad, ""really""",1200,400,10,4096"#,
+            ]
+        );
+        assert_eq!(Format::Csv.header(), Some(Record::CSV_HEADER));
+    }
+
+    #[test]
+    fn parse_failure() {
+        let result = Err(anyhow!("unable to parse"));
+        let records = Record::all(3, &InputSource::Stdin, Parts::Only(2), &result, None);
+        assert_eq!(records.len(), 1);
+        assert_eq!((records[0].part, records[0].status), (2, "error"));
+        assert_eq!(records[0].parse_ns, None);
+    }
+}
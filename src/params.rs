@@ -0,0 +1,85 @@
+use anyhow::{anyhow, bail, Context};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Puzzle constants of a solver, their defaults being the ones of the actual puzzle.
+/// Smaller values from the puzzle statements can be set with `--param name=value`.
+pub trait Params: Default + Send + Sync + 'static {
+    fn set(&mut self, name: &str, value: &str) -> anyhow::Result<()>;
+
+    /// The defaults with the given parameters set.
+    fn with(params: &[Param]) -> anyhow::Result<Self> {
+        let mut r = Self::default();
+        for p in params {
+            r.set(&p.name, &p.value)
+                .with_context(|| format!("invalid parameter {p}"))?;
+        }
+        Ok(r)
+    }
+}
+
+/// For solvers without parameters.
+impl Params for () {
+    fn set(&mut self, name: &str, _value: &str) -> anyhow::Result<()> {
+        bail!("unknown parameter '{name}', this day has none")
+    }
+}
+
+/// A parameter given on the command line as `name=value`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Param {
+    pub name: String,
+    pub value: String,
+}
+
+impl FromStr for Param {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = s
+            .split_once('=')
+            .ok_or(anyhow!("expected name=value, got '{s}'"))?;
+        Ok(Self {
+            name: name.trim().to_string(),
+            value: value.trim().to_string(),
+        })
+    }
+}
+
+impl Display for Param {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.name, self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, Eq, PartialEq)]
+    struct Expansion {
+        factor: u64,
+    }
+
+    impl Params for Expansion {
+        fn set(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+            match name {
+                "factor" => self.factor = value.parse()?,
+                _ => bail!("unknown parameter '{name}'"),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn params() {
+        let p = "factor=10".parse::<Param>().unwrap();
+        assert_eq!(Expansion::with(&[p]).unwrap(), Expansion { factor: 10 });
+        assert_eq!(Expansion::with(&[]).unwrap(), Expansion::default());
+
+        let bad = Expansion::with(&["factor=ten".parse().unwrap()]).unwrap_err();
+        assert_eq!(format!("{bad}"), "invalid parameter factor=ten");
+        assert!(<()>::with(&["factor=10".parse().unwrap()]).is_err());
+        assert!("factor".parse::<Param>().is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use anyhow::Context;
 use std::io::{BufRead, BufReader, Read};
 use std::str::FromStr;
 
@@ -29,46 +30,94 @@ where
     }
 }
 
+/// Splits input into items, failing on read errors, invalid UTF-8 and items
+/// that don't parse, with their position as context.
 pub trait ReadExt<T> {
-    fn split_by<B: FromIterator<T>>(self, separator: u8) -> B;
-    fn split_commas<B: FromIterator<T>>(self) -> B;
-    fn split_lines<B: FromIterator<T>>(self) -> B;
-    fn split_groups<B: FromIterator<T>>(self) -> B;
+    // no solved day reads comma separated input yet
+    #[allow(dead_code)]
+    fn split_by<B: FromIterator<T>>(self, separator: u8) -> anyhow::Result<B>;
+    #[allow(dead_code)]
+    fn split_commas<B: FromIterator<T>>(self) -> anyhow::Result<B>;
+    fn split_lines<B: FromIterator<T>>(self) -> anyhow::Result<B>;
+    fn split_groups<B: FromIterator<T>>(self) -> anyhow::Result<B>;
 }
 
 impl<R, T> ReadExt<T> for R
 where
     R: Read,
     T: FromStr,
+    T::Err: Into<anyhow::Error>,
 {
-    fn split_by<B: FromIterator<T>>(self, separator: u8) -> B {
+    fn split_by<B: FromIterator<T>>(self, separator: u8) -> anyhow::Result<B> {
         BufReader::new(self)
             .split(separator)
-            .flatten()
-            .flat_map(String::from_utf8)
-            .flat_map(|s| s.parse())
+            .enumerate()
+            .map(|(i, s)| {
+                let s = String::from_utf8(s?)?;
+                s.trim()
+                    .parse()
+                    .map_err(Into::into)
+                    .with_context(|| format!("item {}", i + 1))
+            })
             .collect()
     }
 
-    fn split_commas<B: FromIterator<T>>(self) -> B {
+    fn split_commas<B: FromIterator<T>>(self) -> anyhow::Result<B> {
         self.split_by(b',')
     }
 
-    fn split_lines<B: FromIterator<T>>(self) -> B {
+    fn split_lines<B: FromIterator<T>>(self) -> anyhow::Result<B> {
         BufReader::new(self)
             .lines()
-            .flatten()
-            .flat_map(|l| l.parse())
+            .enumerate()
+            .map(|(i, l)| {
+                l?.parse()
+                    .map_err(Into::into)
+                    .with_context(|| format!("line {}", i + 1))
+            })
             .collect()
     }
 
-    fn split_groups<B: FromIterator<T>>(self) -> B {
+    fn split_groups<B: FromIterator<T>>(self) -> anyhow::Result<B> {
         BufReader::new(self)
             .lines()
-            .flatten()
-            .collect::<Vec<_>>()
+            .collect::<Result<Vec<_>, _>>()?
             .split(|l| l.is_empty())
-            .flat_map(|e| e.join("\n").parse())
+            .enumerate()
+            .map(|(i, e)| {
+                e.join("\n")
+                    .parse()
+                    .map_err(Into::into)
+                    .with_context(|| format!("group {}", i + 1))
+            })
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_errors() {
+        let lines: Vec<u32> = "1\n2\n3\n".as_bytes().split_lines().unwrap();
+        assert_eq!(lines, [1, 2, 3]);
+        let commas: Vec<u32> = "1,2,3\n".as_bytes().split_commas().unwrap();
+        assert_eq!(commas, [1, 2, 3]);
+        let groups: Vec<String> = "a\nb\n\nc".as_bytes().split_groups().unwrap();
+        assert_eq!(groups, ["a\nb", "c"]);
+
+        let bad = "1\nx\n3\n".as_bytes().split_lines::<Vec<u32>>();
+        assert_eq!(
+            format!("{:#}", bad.unwrap_err()),
+            "line 2: invalid digit found in string"
+        );
+        let bad = "1,x".as_bytes().split_commas::<Vec<u32>>();
+        assert_eq!(format!("{}", bad.unwrap_err()), "item 2");
+
+        let invalid = b"1\n\xff\n3\n";
+        assert!(invalid.split_lines::<Vec<u32>>().is_err());
+        assert!(invalid.split_commas::<Vec<u32>>().is_err());
+        assert!(invalid.split_groups::<Vec<String>>().is_err());
+    }
+}
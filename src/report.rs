@@ -0,0 +1,200 @@
+use crate::alloc::format_bytes;
+use crate::bench::round;
+use humantime::format_duration;
+use std::time::Duration;
+
+pub const README_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/README.md");
+
+const START_MARKER: &str = "<!-- results:start -->";
+const END_MARKER: &str = "<!-- results:end -->";
+
+/// A day in the results table, answers themselves are never shown.
+#[derive(Clone, Debug, Default)]
+pub struct Row {
+    pub day: u32,
+    /// `None` when parsing failed.
+    pub parse_time: Option<Duration>,
+    /// Only counted in the total, `None` when the solver shares no work between parts.
+    pub prepare_time: Option<Duration>,
+    /// Status and time of each part, `None` when not solved.
+    pub parts: [Option<(String, Duration)>; 2],
+    /// Shown as the status of both parts when the day couldn't be solved,
+    /// `missing input` or `error`.
+    pub failure: Option<&'static str>,
+    /// Peak RSS while solving the day, `None` outside of Linux.
+    pub rss: Option<usize>,
+}
+
+impl Row {
+    fn total(&self) -> Duration {
+        let parts = self.parts.iter().flatten().map(|(_, t)| *t);
+        self.parse_time
+            .into_iter()
+            .chain(self.prepare_time)
+            .chain(parts)
+            .sum()
+    }
+}
+
+pub fn markdown_table(rows: &[Row]) -> String {
+    let time =
+        |t: Option<Duration>| t.map_or("-".to_string(), |t| format_duration(round(t)).to_string());
+    let status = |r: &Row, i: usize| match (&r.parts[i], r.failure) {
+        (Some((s, _)), _) => s.clone(),
+        (None, Some(failure)) => failure.to_string(),
+        (None, None) => "-".to_string(),
+    };
+    let part_time = |p: &Option<(String, Duration)>| time(p.as_ref().map(|(_, t)| *t));
+
+    let mut lines = vec![
+        "| Day | Part 1 | Part 2 | Parsing | Part 1 time | Part 2 time | Total | Peak RSS |"
+            .to_string(),
+        "|----:|--------|--------|--------:|------------:|------------:|------:|---------:|"
+            .to_string(),
+    ];
+    for r in rows {
+        let cells = [
+            r.day.to_string(),
+            status(r, 0),
+            status(r, 1),
+            time(r.parse_time),
+            part_time(&r.parts[0]),
+            part_time(&r.parts[1]),
+            time(r.parse_time.map(|_| r.total())),
+            r.rss.map_or("-".to_string(), format_bytes),
+        ];
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+
+    let sum = |f: &dyn Fn(&Row) -> Option<Duration>| rows.iter().filter_map(f).sum();
+    let totals = [
+        sum(&|r| r.parse_time),
+        sum(&|r| r.parts[0].as_ref().map(|(_, t)| *t)),
+        sum(&|r| r.parts[1].as_ref().map(|(_, t)| *t)),
+        sum(&|r| Some(r.total())),
+    ];
+    let peak = rows.iter().filter_map(|r| r.rss).max();
+    lines.push(format!(
+        "| **Total** | | | {} | {} |",
+        totals.map(|t| time(Some(t))).join(" | "),
+        peak.map_or("-".to_string(), format_bytes)
+    ));
+
+    lines.join("\n")
+}
+
+/// Offset of the first line only made of `marker`, so that markers quoted in the
+/// text around are left alone.
+fn find_line(s: &str, marker: &str) -> Option<usize> {
+    let mut offset = 0;
+    for l in s.split_inclusive('\n') {
+        if l.trim_end() == marker {
+            return Some(offset);
+        }
+        offset += l.len();
+    }
+    None
+}
+
+/// Replaces the table between the marker comments,
+/// adding a results section at the end if there are none.
+pub fn update_readme(readme: &str, table: &str) -> anyhow::Result<String> {
+    let block = format!("{START_MARKER}\n{table}\n{END_MARKER}");
+
+    match (
+        find_line(readme, START_MARKER),
+        find_line(readme, END_MARKER),
+    ) {
+        (Some(start), Some(end)) if start < end => Ok(format!(
+            "{}{block}{}",
+            &readme[..start],
+            &readme[end + END_MARKER.len()..]
+        )),
+        (None, None) => Ok(format!("{}\n\n## Results\n\n{block}\n", readme.trim_end())),
+        _ => anyhow::bail!("mismatched {START_MARKER} and {END_MARKER} markers"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<Row> {
+        let us = Duration::from_micros;
+        vec![
+            Row {
+                day: 1,
+                parse_time: Some(us(20)),
+                prepare_time: Some(us(4)),
+                parts: [
+                    Some(("verified".to_string(), us(5))),
+                    Some(("unsolved".to_string(), us(1))),
+                ],
+                failure: None,
+                rss: Some(3 << 20),
+            },
+            Row {
+                day: 2,
+                ..Default::default()
+            },
+            Row {
+                day: 3,
+                failure: Some("missing input"),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn table() {
+        let table = markdown_table(&rows());
+        assert_eq!(
+            table.lines().skip(2).collect::<Vec<_>>(),
+            [
+                "| 1 | verified | unsolved | 20us | 5us | 1us | 30us | 3.0 MiB |",
+                "| 2 | - | - | - | - | - | - | - |",
+                "| 3 | missing input | missing input | - | - | - | - | - |",
+                "| **Total** | | | 20us | 5us | 1us | 30us | 3.0 MiB |",
+            ]
+        );
+    }
+
+    #[test]
+    fn readme() {
+        let readme = "# AoC\n\n<!-- results:start -->\nold\n<!-- results:end -->\n\nMore\n";
+        assert_eq!(
+            update_readme(readme, "new").unwrap(),
+            "# AoC\n\n<!-- results:start -->\nnew\n<!-- results:end -->\n\nMore\n"
+        );
+
+        let updated = update_readme("# AoC\n", "new").unwrap();
+        assert_eq!(
+            updated,
+            "# AoC\n\n## Results\n\n<!-- results:start -->\nnew\n<!-- results:end -->\n"
+        );
+        assert_eq!(update_readme(&updated, "new").unwrap(), updated);
+
+        assert!(update_readme("<!-- results:end -->\n<!-- results:start -->", "new").is_err());
+
+        let quoted = "Between `<!-- results:start -->` and `<!-- results:end -->`.\n";
+        assert_eq!(
+            update_readme(quoted, "new").unwrap(),
+            format!(
+                "{}\n\n## Results\n\n{START_MARKER}\nnew\n{END_MARKER}\n",
+                quoted.trim_end()
+            )
+        );
+    }
+
+    #[test]
+    fn this_readme() {
+        let readme = include_str!("../README.md");
+        let updated = update_readme(readme, "new").unwrap();
+        let untouched = match find_line(readme, START_MARKER) {
+            Some(start) => &readme[..start],
+            None => readme.trim_end(),
+        };
+        assert!(updated.starts_with(untouched));
+        assert_eq!(update_readme(&updated, "new").unwrap(), updated);
+    }
+}
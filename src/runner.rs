@@ -0,0 +1,661 @@
+use crate::alloc::format_bytes;
+use crate::answer::Answer;
+use crate::answers::{Answers, ANSWERS_FILE};
+use crate::bench::{pin_to_cpu, process_cpu_time, round, thread_counts, Change, Samples, Stats};
+use crate::cli::{BenchArgs, DayArgs, ScalingArgs};
+use crate::examples::{Example, EXAMPLES_DIR};
+use crate::history::{git_revision, input_hash, settings, Entry, History, HISTORY_FILE};
+use crate::input::InputSource;
+use crate::memory::{peak_rss, reset_peak_rss};
+use crate::output::{Format, Record};
+use crate::report::{markdown_table, update_readme, Row, README_FILE};
+use crate::scaffold::{Scaffold, SOLUTIONS_DIR};
+use crate::solver::{solve, solved_days, solver_for, timing, DayResult, Parts, SolveOptions};
+use crate::table::Table;
+use anyhow::{anyhow, bail, Context};
+use humantime::format_duration;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// An input to solve a day on, examples coming with their expected answers.
+struct DayInput {
+    source: InputSource,
+    parts: Parts,
+    expected: Option<BTreeMap<u8, Answer>>,
+}
+
+fn selected_days(args: &DayArgs) -> anyhow::Result<Vec<u32>> {
+    let solved = solved_days();
+    let days = args.days.resolve(&solved);
+    if args.input.is_some() && days.len() != 1 {
+        bail!("an explicit input requires selecting exactly one day");
+    }
+    if !args.params.is_empty() && days.len() != 1 {
+        bail!("parameters require selecting exactly one day, as they differ between days");
+    }
+    Ok(days
+        .into_iter()
+        .filter(|&day| {
+            let is_solved = solved.contains(&day);
+            if !is_solved {
+                eprintln!("Day {day} hasn't been solved yet :(");
+            }
+            is_solved
+        })
+        .collect())
+}
+
+/// The main input of a day, or the one given on the command line.
+fn input_source(day: u32, args: &DayArgs) -> InputSource {
+    args.input
+        .as_ref()
+        .map_or_else(|| InputSource::for_day(day), InputSource::from_arg)
+}
+
+/// Every input of a day, or the one given on the command line.
+fn input_sources(day: u32, args: &DayArgs) -> Vec<InputSource> {
+    if args.input.is_some() {
+        return vec![input_source(day, args)];
+    }
+
+    let sources = InputSource::all_for_day(day);
+    if sources.is_empty() {
+        // will be reported as a missing file
+        vec![InputSource::for_day(day)]
+    } else {
+        sources
+    }
+}
+
+/// The inputs to solve a day on, or its examples with `--examples`.
+/// Examples without an answer for the selected part are skipped.
+fn day_inputs(day: u32, args: &DayArgs) -> anyhow::Result<Vec<DayInput>> {
+    let parts = Parts::from(args.part);
+
+    if !args.examples {
+        return Ok(input_sources(day, args)
+            .into_iter()
+            .map(|source| DayInput {
+                source,
+                parts,
+                expected: None,
+            })
+            .collect());
+    }
+
+    Ok(Example::all_for_day(day)?
+        .into_iter()
+        .filter_map(|e| {
+            Some(DayInput {
+                parts: e.parts().intersect(parts)?,
+                source: e.source,
+                expected: Some(e.expected),
+            })
+        })
+        .collect())
+}
+
+fn solve_options(input: &DayInput, args: &DayArgs) -> SolveOptions {
+    SolveOptions {
+        parts: input.parts,
+        timeout: args.timeout.map(Into::into),
+        threads: args.threads,
+    }
+}
+
+fn solve_input(day: u32, input: &DayInput, args: &DayArgs) -> anyhow::Result<DayResult> {
+    solve_with(day, input, args, solve_options(input, args))
+}
+
+fn solve_with(
+    day: u32,
+    input: &DayInput,
+    args: &DayArgs,
+    options: SolveOptions,
+) -> anyhow::Result<DayResult> {
+    let solver = solver_for(day).ok_or(anyhow!("day {day} hasn't been solved yet"))?;
+    solve(solver, &input.source, &args.params, options)
+}
+
+pub fn run(args: &DayArgs, format: Format, parallel: bool) -> anyhow::Result<()> {
+    if let Some(header) = format.header() {
+        println!("{header}");
+    }
+
+    let days = selected_days(args)?;
+    if !parallel {
+        for day in days {
+            run_day(day, args, format, true).print();
+        }
+        return Ok(());
+    }
+
+    // days are solved side by side, their output is printed once all are done
+    let start = Instant::now();
+    let cpu_start = process_cpu_time();
+    reset_peak_rss();
+    let outputs = days
+        .par_iter()
+        .map(|&day| run_day(day, args, format, false))
+        .collect::<Vec<_>>();
+    let wall_clock = start.elapsed();
+    let cpu_time = process_cpu_time()
+        .zip(cpu_start)
+        .map(|(end, start)| end - start);
+
+    for output in &outputs {
+        output.print();
+    }
+    let summed = outputs.iter().map(|o| o.time).sum::<Duration>();
+    let mut summary = format!("Wall-clock: {}", format_duration(round(wall_clock)));
+    if let Some(t) = cpu_time {
+        summary.push_str(&format!(", CPU time: {}", format_duration(round(t))));
+    }
+    summary.push_str(&format!(
+        ", days' wall-clock summed: {}",
+        format_duration(round(summed))
+    ));
+    if let Some(rss) = peak_rss() {
+        summary.push_str(&format!(", peak RSS: {}", format_bytes(rss)));
+    }
+    if format == Format::Text {
+        println!("{summary}");
+    } else {
+        eprintln!("{summary}");
+    }
+
+    Ok(())
+}
+
+/// Rendered results of a day, printed once the day is done.
+struct DayOutput {
+    out: String,
+    /// Errors preventing from solving the day, for stderr.
+    err: String,
+    /// Wall-clock time of the day.
+    time: Duration,
+}
+
+impl DayOutput {
+    fn print(&self) {
+        print!("{}", self.out);
+        eprint!("{}", self.err);
+    }
+}
+
+/// Solves a day and renders its results, along with the time it took.
+/// Without `isolated`, other days run at the same time and the peak RSS
+/// is the one of the whole run so far.
+fn run_day(day: u32, args: &DayArgs, format: Format, isolated: bool) -> DayOutput {
+    let isolated = isolated && reset_peak_rss();
+    let start = Instant::now();
+    let mut out = String::new();
+
+    if format == Format::Text {
+        out.push_str(&format!("Day {day}:\n"));
+    }
+    let inputs = match day_inputs(day, args) {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            return DayOutput {
+                out,
+                err: format!("Error: {:#}\n", e),
+                time: start.elapsed(),
+            }
+        }
+    };
+
+    let results = inputs
+        .into_iter()
+        .map(|input| {
+            let result = solve_input(day, &input, args);
+            (input, result)
+        })
+        .collect::<Vec<_>>();
+    let time = start.elapsed();
+    let rss = peak_rss();
+
+    if format != Format::Text {
+        let rss = rss.filter(|_| isolated);
+        for (input, result) in &results {
+            Record::all(day, &input.source, input.parts, result, rss)
+                .iter()
+                .filter_map(|r| format.record(r))
+                .for_each(|line| out.push_str(&format!("{line}\n")));
+        }
+        return DayOutput {
+            out,
+            err: String::new(),
+            time,
+        };
+    }
+
+    let text = match results.as_slice() {
+        [] => "No examples".to_string(),
+        [(_, Ok(r))] if !args.examples => r.to_string(),
+        [(_, Err(e))] if !args.examples => format!("Error: {:#}", e),
+        results => results_table(results).to_string(),
+    };
+    out.push_str(&format!("{text}\n"));
+    match rss {
+        Some(rss) if isolated => out.push_str(&format!("Peak RSS: {}\n", format_bytes(rss))),
+        Some(rss) => out.push_str(&format!("Peak RSS so far: {}\n", format_bytes(rss))),
+        None => {}
+    }
+
+    DayOutput {
+        out,
+        err: String::new(),
+        time,
+    }
+}
+
+fn results_table(results: &[(DayInput, anyhow::Result<DayResult>)]) -> Table {
+    let mut table = Table::new(["Input", "Parsing", "Solution 1", "Solution 2"]);
+
+    for (input, result) in results {
+        let mut row = vec![input.source.name()];
+        match result {
+            Ok(r) => {
+                let mut parsing = timing(r.parse_time, r.parse_allocs);
+                if let Some(t) = r.prepare_time {
+                    parsing.push_str(&format!(" + {} preparing", timing(t, r.prepare_allocs)));
+                }
+                row.push(parsing);
+                for p in [&r.first, &r.second] {
+                    row.push(p.as_ref().map_or_else(
+                        || "-".to_string(),
+                        |p| match p.answer {
+                            Ok(_) => format!("{} ({})", p.summary(), timing(p.time, p.allocs)),
+                            Err(_) => p.summary(),
+                        },
+                    ));
+                }
+            }
+            Err(e) => row.push(format!("Error: {:#}", e)),
+        }
+        table.push(row);
+    }
+
+    table
+}
+
+pub fn list() {
+    for day in solved_days() {
+        let inputs = InputSource::all_for_day(day);
+        if inputs.is_empty() {
+            println!("Day {day:>2}: missing input");
+        } else {
+            let names = inputs.iter().map(InputSource::name).collect::<Vec<_>>();
+            println!("Day {day:>2}: {}", names.join(", "));
+        }
+    }
+}
+
+pub fn new(day: u32) -> anyhow::Result<()> {
+    let scaffold = Scaffold::new(day, SOLUTIONS_DIR, EXAMPLES_DIR);
+    for p in scaffold.create()? {
+        println!("Created {}", p.display());
+    }
+    println!("Day {day} is registered on the next build");
+    Ok(())
+}
+
+pub fn check(args: &DayArgs, record: bool) -> anyhow::Result<()> {
+    if record && args.examples {
+        bail!("the answers of examples are stored next to them and can't be recorded");
+    }
+    if record && !args.params.is_empty() {
+        bail!("answers can't be recorded with --param, they are the ones of the actual puzzle");
+    }
+
+    let mut answers = if args.examples {
+        Answers::default()
+    } else if record {
+        Answers::load_or_default(ANSWERS_FILE)?
+    } else {
+        Answers::load(ANSWERS_FILE)?
+    };
+    let mut failures = 0;
+
+    for day in selected_days(args)? {
+        println!("Day {day}:");
+        let inputs = day_inputs(day, args)?;
+        if inputs.is_empty() {
+            println!("No examples");
+        }
+
+        for day_input in &inputs {
+            let input = day_input.source.name();
+            let prefix = if inputs.len() > 1 || args.examples {
+                format!("{input}: ")
+            } else {
+                String::new()
+            };
+
+            let r = match solve_input(day, day_input, args) {
+                Ok(r) => r,
+                Err(e) => {
+                    println!("{prefix}Error: {:#}", e);
+                    failures += 1;
+                    continue;
+                }
+            };
+
+            for (part, res) in r.parts() {
+                let Ok(answer) = &res.answer else {
+                    println!("{prefix}Solution {part}: {}", res.summary());
+                    failures += 1;
+                    continue;
+                };
+
+                if record {
+                    answers.record(day, &input, part, answer);
+                    println!("{prefix}Solution {part}: recorded");
+                    continue;
+                }
+
+                let verified = match &day_input.expected {
+                    Some(expected) => expected.get(&part).map(|e| e == answer),
+                    None => answers.verify(day, &input, part, answer),
+                };
+                match verified {
+                    Some(true) => println!("{prefix}Solution {part}: verified"),
+                    Some(false) => {
+                        println!("{prefix}Solution {part}: mismatch");
+                        failures += 1;
+                    }
+                    None => println!("{prefix}Solution {part}: no stored answer"),
+                }
+            }
+        }
+    }
+
+    if record {
+        answers.save(ANSWERS_FILE)?;
+        println!("Answers recorded in {ANSWERS_FILE}");
+    }
+
+    if failures > 0 {
+        bail!("{failures} check(s) failed");
+    }
+
+    Ok(())
+}
+
+pub fn report(args: &DayArgs, markdown: bool) -> anyhow::Result<()> {
+    let answers = Answers::load_or_default(ANSWERS_FILE)?;
+    let mut rows = vec![];
+
+    for day in selected_days(args)? {
+        // only the main input of each day
+        let Some(input) = day_inputs(day, args)?.into_iter().next() else {
+            continue;
+        };
+        let mut row = Row {
+            day,
+            ..Default::default()
+        };
+
+        let isolated = reset_peak_rss();
+        let result = solve_input(day, &input, args);
+        row.rss = peak_rss().filter(|_| isolated);
+        let r = match result {
+            Ok(r) => r,
+            Err(_) => {
+                row.failure = Some(match &input.source {
+                    InputSource::File(p) if !p.exists() => "missing input",
+                    _ => "error",
+                });
+                rows.push(row);
+                continue;
+            }
+        };
+        row.parse_time = Some(r.parse_time);
+        row.prepare_time = r.prepare_time;
+        for (part, p) in r.parts() {
+            let verified = p
+                .answer
+                .as_ref()
+                .ok()
+                .and_then(|answer| match &input.expected {
+                    Some(expected) => expected.get(&part).map(|e| e == answer),
+                    None => answers.verify(day, &input.source.name(), part, answer),
+                });
+            let status = match verified {
+                Some(true) => "verified",
+                Some(false) => "mismatch",
+                None => p.status(),
+            };
+            row.parts[part as usize - 1] = Some((status.to_string(), p.time));
+        }
+        rows.push(row);
+    }
+
+    let table = markdown_table(&rows);
+    if !markdown {
+        println!("{table}");
+        return Ok(());
+    }
+
+    let readme =
+        fs::read_to_string(README_FILE).with_context(|| format!("unable to read {README_FILE}"))?;
+    fs::write(README_FILE, update_readme(&readme, &table)?)
+        .with_context(|| format!("unable to write {README_FILE}"))?;
+    println!("Results table updated in {README_FILE}");
+
+    Ok(())
+}
+
+pub fn bench(args: &DayArgs, options: &BenchArgs) -> anyhow::Result<()> {
+    if let Some(cpu) = options.pin {
+        pin_to_cpu(cpu)?;
+    }
+
+    let history = History::load_or_default(HISTORY_FILE)?;
+    let revision = git_revision();
+    let settings = settings(options.pin, args.threads, &args.params)?;
+    let mut entries = vec![];
+    let mut regressions = 0;
+
+    for day in selected_days(args)? {
+        let inputs = day_inputs(day, args)?;
+        if inputs.is_empty() {
+            println!("Day {day}:");
+            println!("No examples");
+        }
+
+        for input in &inputs {
+            let header = if inputs.len() > 1 || args.examples {
+                format!("Day {day}, {}", input.source.name())
+            } else {
+                format!("Day {day}")
+            };
+
+            match bench_input(day, input, args, options) {
+                Ok(samples) => {
+                    println!(
+                        "{header} ({} runs after {} warmup):",
+                        options.iterations, options.warmup
+                    );
+                    println!("{}", samples.table());
+
+                    let input = input_hash(&input.source)?;
+                    let new = samples.phases().map(|(phase, samples)| Entry {
+                        day,
+                        phase,
+                        input: input.clone(),
+                        revision: revision.clone(),
+                        settings: settings.clone(),
+                        samples: samples.to_vec(),
+                    });
+                    let start = entries.len();
+                    entries.extend(new);
+
+                    if let Some(baseline) = &options.compare {
+                        match comparison_table(&history, &entries[start..], baseline.as_deref()) {
+                            Some((table, n)) => {
+                                println!("{table}");
+                                regressions += n;
+                            }
+                            None => println!("No stored timings to compare with"),
+                        }
+                    }
+                }
+                Err(e) => {
+                    println!("{header}:");
+                    println!("Error: {:#}", e);
+                }
+            }
+        }
+    }
+
+    History::append(HISTORY_FILE, &entries)?;
+    println!("Timings saved in {HISTORY_FILE}");
+
+    if regressions > 0 {
+        bail!("{regressions} phase(s) got significantly slower");
+    }
+
+    Ok(())
+}
+
+/// Compares new timings with the stored ones, returning the number of regressions.
+/// `None` if none of the phases has stored timings.
+fn comparison_table(
+    history: &History,
+    entries: &[Entry],
+    revision: Option<&str>,
+) -> Option<(Table, usize)> {
+    let mut table = Table::new(["Phase", "Baseline", "Change", "Welch t", ""]);
+    let mut regressions = 0;
+    let mut compared = false;
+
+    for entry in entries {
+        let mut row = vec![entry.phase.to_string()];
+        let baseline = history.baseline(entry, revision);
+        match baseline.and_then(|b| Some((b, Change::new(&b.samples, &entry.samples)?))) {
+            Some((baseline, change)) => {
+                compared = true;
+                row.push(baseline.revision.clone());
+                row.push(change.to_string());
+                row.push(format!("{:.2}", change.t));
+                if change.is_regression() {
+                    row.push("significantly slower".to_string());
+                    regressions += 1;
+                } else {
+                    row.push(String::new());
+                }
+            }
+            None => row.extend(["-", "-", "-", ""].map(String::from)),
+        }
+        table.push(row);
+    }
+
+    compared.then_some((table, regressions))
+}
+
+pub fn scaling(args: &DayArgs, options: &ScalingArgs) -> anyhow::Result<()> {
+    let max_threads = options
+        .max_threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, usize::from));
+
+    for day in selected_days(args)? {
+        // only the main input of each day
+        let Some(input) = day_inputs(day, args)?.into_iter().next() else {
+            continue;
+        };
+        println!("Day {day} (median of {} runs):", options.iterations);
+
+        match scaling_table(day, &input, args, options, max_threads) {
+            Ok(table) => println!("{table}"),
+            Err(e) => println!("Error: {:#}", e),
+        }
+    }
+
+    Ok(())
+}
+
+fn scaling_table(
+    day: u32,
+    input: &DayInput,
+    args: &DayArgs,
+    options: &ScalingArgs,
+    max_threads: usize,
+) -> anyhow::Result<Table> {
+    let mut table = Table::new([
+        "Threads",
+        "Part 1",
+        "Speedup",
+        "Efficiency",
+        "Part 2",
+        "Speedup",
+        "Efficiency",
+    ]);
+    let mut single_thread = [None; 2];
+
+    for threads in thread_counts(max_threads) {
+        let mut samples = Samples::default();
+        for _ in 0..options.iterations {
+            let Some(parts) = samples.pending(input.parts) else {
+                break;
+            };
+            let solve_options = SolveOptions {
+                parts,
+                threads: Some(threads),
+                ..solve_options(input, args)
+            };
+            samples.push(&solve_with(day, input, args, solve_options)?);
+        }
+
+        let mut row = vec![threads.to_string()];
+        for (i, baseline) in single_thread.iter_mut().enumerate() {
+            let median = Stats::new(&samples.parts[i]).map(|s| s.median);
+            let cells = match (&samples.failures[i], median) {
+                (Some(failure), _) => [failure.clone(), "-".into(), "-".into()],
+                (None, Some(t)) => {
+                    let speedup = baseline.get_or_insert(t).as_secs_f64() / t.as_secs_f64();
+                    [
+                        format_duration(round(t)).to_string(),
+                        format!("{speedup:.2}x"),
+                        format!("{:.0}%", speedup / threads as f64 * 100.0),
+                    ]
+                }
+                (None, None) => ["-", "-", "-"].map(String::from),
+            };
+            row.extend(cells);
+        }
+        table.push(row);
+    }
+
+    Ok(table)
+}
+
+fn bench_input(
+    day: u32,
+    input: &DayInput,
+    args: &DayArgs,
+    options: &BenchArgs,
+) -> anyhow::Result<Samples> {
+    let mut samples = Samples::default();
+    for i in 0..options.warmup + options.iterations {
+        let Some(parts) = samples.pending(input.parts) else {
+            break;
+        };
+        let solve_options = SolveOptions {
+            parts,
+            ..solve_options(input, args)
+        };
+        let r = solve_with(day, input, args, solve_options)?;
+        if i < options.warmup {
+            samples.push_failures(&r);
+        } else {
+            samples.push(&r);
+        }
+    }
+
+    Ok(samples)
+}
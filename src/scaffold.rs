@@ -0,0 +1,84 @@
+use anyhow::{bail, Context};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Where the build script looks for the solutions to register.
+pub const SOLUTIONS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/solutions");
+
+const SOLUTION_TEMPLATE: &str = include_str!("../solution.rs.template");
+
+/// Files to create for a new day.
+#[derive(Debug)]
+pub struct Scaffold {
+    pub solution: PathBuf,
+    pub example: PathBuf,
+}
+
+impl Scaffold {
+    pub fn new<P: AsRef<Path>>(day: u32, solutions_dir: P, examples_dir: P) -> Self {
+        Self {
+            solution: solutions_dir.as_ref().join(format!("day{day:02}.rs")),
+            example: examples_dir.as_ref().join(format!("{day:02}-1.txt")),
+        }
+    }
+
+    /// Creates the solution with its test module and the example, refusing to
+    /// overwrite any of them. Returns the created files.
+    pub fn create(&self) -> anyhow::Result<Vec<&Path>> {
+        let existing = [&self.solution, &self.example]
+            .into_iter()
+            .filter(|p| p.exists())
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>();
+        if !existing.is_empty() {
+            bail!("refusing to overwrite {}", existing.join(" and "));
+        }
+
+        let mut created = vec![];
+        for (p, contents) in [(&self.solution, SOLUTION_TEMPLATE), (&self.example, "")] {
+            create_new(p, contents)?;
+            created.push(p.as_path());
+        }
+
+        Ok(created)
+    }
+}
+
+fn create_new(p: &Path, contents: &str) -> anyhow::Result<()> {
+    if let Some(dir) = p.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("unable to create {}", dir.display()))?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(p)
+        .and_then(|mut f| f.write_all(contents.as_bytes()))
+        .with_context(|| format!("unable to create {}", p.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+
+    #[test]
+    fn create() {
+        let dir = temp_dir().join("aoc-scaffold-test");
+        let _ = fs::remove_dir_all(&dir);
+        let scaffold = Scaffold::new(12, dir.join("solutions"), dir.join("examples"));
+
+        assert_eq!(scaffold.create().unwrap().len(), 2);
+        let solution = fs::read_to_string(&scaffold.solution).unwrap();
+        assert!(solution.contains("impl Solver for Problem"));
+        assert!(solution.contains("mod tests"));
+        assert!(solution.contains("#[ignore"));
+        assert_eq!(fs::read_to_string(&scaffold.example).unwrap(), "");
+
+        fs::write(&scaffold.solution, "solved").unwrap();
+        assert!(scaffold.create().is_err());
+        assert_eq!(fs::read_to_string(&scaffold.solution).unwrap(), "solved");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
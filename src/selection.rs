@@ -0,0 +1,131 @@
+use anyhow::{anyhow, bail};
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+const FIRST_DAY: u32 = 1;
+const LAST_DAY: u32 = 25;
+
+/// A comma separated list of days, inclusive ranges, `latest` or `all`,
+/// e.g. `1,4,9`, `3-7` or `1-3,latest`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaySelection(Vec<Item>);
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Item {
+    All,
+    Latest,
+    Range(u32, u32),
+}
+
+impl DaySelection {
+    /// Resolves the selection against the available days, in order and without duplicates.
+    /// Explicitly requested days are kept even if they are not available.
+    pub fn resolve(&self, available: &[u32]) -> Vec<u32> {
+        let mut days = BTreeSet::new();
+
+        for item in &self.0 {
+            match *item {
+                Item::All => days.extend(available),
+                Item::Latest => days.extend(available.iter().max()),
+                Item::Range(first, last) if first == last => {
+                    days.insert(first);
+                }
+                Item::Range(first, last) => {
+                    days.extend(available.iter().filter(|d| (first..=last).contains(d)))
+                }
+            }
+        }
+
+        days.into_iter().collect()
+    }
+}
+
+impl Default for DaySelection {
+    fn default() -> Self {
+        Self(vec![Item::All])
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .map(Item::from_str)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl FromStr for Item {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Self::All),
+            "latest" => Ok(Self::Latest),
+            s => {
+                let (first, last) = s.split_once('-').unwrap_or((s, s));
+                let first = parse_day(first)?;
+                let last = parse_day(last)?;
+                if first > last {
+                    bail!("invalid range {s}");
+                }
+                Ok(Self::Range(first, last))
+            }
+        }
+    }
+}
+
+fn parse_day(s: &str) -> anyhow::Result<u32> {
+    let day = s
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("invalid day '{s}', expected a number, a range, 'latest' or 'all'"))?;
+    if !(FIRST_DAY..=LAST_DAY).contains(&day) {
+        bail!("day {day} is out of range {FIRST_DAY}-{LAST_DAY}");
+    }
+    Ok(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AVAILABLE: &[u32] = &[1, 2, 3, 5, 8];
+
+    fn resolve(s: &str) -> Vec<u32> {
+        DaySelection::from_str(s).unwrap().resolve(AVAILABLE)
+    }
+
+    #[test]
+    fn single_days() {
+        assert_eq!(resolve("3"), vec![3]);
+        assert_eq!(resolve("9,1,4"), vec![1, 4, 9]);
+        assert_eq!(resolve("2,2"), vec![2]);
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(resolve("2-6"), vec![2, 3, 5]);
+        assert_eq!(resolve("1-2,7-25"), vec![1, 2, 8]);
+    }
+
+    #[test]
+    fn keywords() {
+        assert_eq!(resolve("all"), AVAILABLE);
+        assert_eq!(resolve("latest"), vec![8]);
+        assert_eq!(resolve("1,latest"), vec![1, 8]);
+        assert_eq!(DaySelection::default().resolve(AVAILABLE), AVAILABLE);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(DaySelection::from_str("day3").is_err());
+        assert!(DaySelection::from_str("0").is_err());
+        assert!(DaySelection::from_str("26").is_err());
+        assert!(DaySelection::from_str("7-3").is_err());
+        assert!(DaySelection::from_str("1,").is_err());
+    }
+}
@@ -0,0 +1,3 @@
+//! Every `src/solutions/dayNN.rs` solution, registered by the build script.
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
@@ -1,5 +1,6 @@
 use crate::parsing::ReadExt;
 use crate::solver::Solver;
+use anyhow::anyhow;
 use std::io::Read;
 
 pub struct Problem;
@@ -8,29 +9,50 @@ impl Solver for Problem {
     type Input = Vec<String>;
     type Output1 = u64;
     type Output2 = u64;
+    type Prepared = ();
+    type Params = ();
 
-    fn parse_input<R: Read>(&self, r: R) -> Self::Input {
+    fn parse_input<R: Read>(&self, r: R) -> anyhow::Result<Self::Input> {
         r.split_lines()
     }
 
-    fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
+    fn prepare(&self, _input: &Self::Input) -> anyhow::Result<Self::Prepared> {
+        Ok(())
+    }
+
+    fn solve_first(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output1> {
         input
             .iter()
-            .filter_map(|s| first_last_number_in_string(s))
-            .map(|(a, b)| digits_to_number(a, b))
+            .enumerate()
+            .map(|(i, s)| {
+                let (a, b) = first_last_number_in_string(s)
+                    .ok_or_else(|| anyhow!("no digit on line {}", i + 1))?;
+                Ok(digits_to_number(a, b))
+            })
             .sum()
     }
 
-    fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
+    fn solve_second(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output2> {
         input
             .iter()
-            .map(|s| numbers_from_str(s))
-            .map(|v| (v.first().cloned(), v.last().cloned()))
-            .filter_map(|t| match t {
-                (Some(a), Some(b)) => Some((a, b)),
-                _ => None,
+            .enumerate()
+            .map(|(i, s)| {
+                let v = numbers_from_str(s);
+                match (v.first(), v.last()) {
+                    (Some(&a), Some(&b)) => Ok(digits_to_number(a, b)),
+                    _ => Err(anyhow!("no digit on line {}", i + 1)),
+                }
             })
-            .map(|(a, b)| digits_to_number(a, b))
             .sum()
     }
 }
@@ -119,8 +141,12 @@ a1b2c3d4e5f
 treb7uchet"
             .as_bytes();
         let p = Problem;
-        let v = p.solve_first(&p.parse_input(i));
+        let v = p.solve_first(&p.parse_input(i).unwrap(), &(), &()).unwrap();
         assert_eq!(v, 142);
+
+        let garbage = p.parse_input("1abc2\ngarbage".as_bytes()).unwrap();
+        let e = p.solve_first(&garbage, &(), &()).unwrap_err();
+        assert_eq!(e.to_string(), "no digit on line 2");
     }
 
     #[test]
@@ -134,7 +160,9 @@ zoneight234
 7pqrstsixteen"
             .as_bytes();
         let p = Problem;
-        let v = p.solve_second(&p.parse_input(i));
+        let v = p
+            .solve_second(&p.parse_input(i).unwrap(), &(), &())
+            .unwrap();
         assert_eq!(v, 281);
     }
 }
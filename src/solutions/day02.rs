@@ -1,6 +1,7 @@
+use crate::params::Params;
 use crate::parsing::ReadExt;
 use crate::solver::Solver;
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use scan_fmt::scan_fmt;
 use std::io::Read;
 use std::str::FromStr;
@@ -11,27 +12,37 @@ impl Solver for Problem {
     type Input = Vec<Game>;
     type Output1 = usize;
     type Output2 = usize;
+    type Prepared = ();
+    type Params = Bag;
 
-    fn parse_input<R: Read>(&self, r: R) -> Self::Input {
+    fn parse_input<R: Read>(&self, r: R) -> anyhow::Result<Self::Input> {
         r.split_lines()
     }
 
-    fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
-        const CUBES: Cubes = Cubes {
-            r: 12,
-            g: 13,
-            b: 14,
-        };
+    fn prepare(&self, _input: &Self::Input) -> anyhow::Result<Self::Prepared> {
+        Ok(())
+    }
 
-        input
+    fn solve_first(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        params: &Self::Params,
+    ) -> anyhow::Result<Self::Output1> {
+        Ok(input
             .iter()
-            .filter(|g| g.is_possible(&CUBES))
+            .filter(|g| g.is_possible(&params.0))
             .map(|g| g.id)
-            .sum()
+            .sum())
     }
 
-    fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
-        input.iter().map(|g| g.min_cubes().power()).sum()
+    fn solve_second(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output2> {
+        Ok(input.iter().map(|g| g.min_cubes().power()).sum())
     }
 }
 
@@ -87,6 +98,32 @@ struct Cubes {
     b: usize,
 }
 
+/// The cubes loaded in the bag, set with `--param red=12` and the like.
+pub struct Bag(Cubes);
+
+impl Default for Bag {
+    fn default() -> Self {
+        Self(Cubes {
+            r: 12,
+            g: 13,
+            b: 14,
+        })
+    }
+}
+
+impl Params for Bag {
+    fn set(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        let n = value.parse()?;
+        match name {
+            "red" => self.0.r = n,
+            "green" => self.0.g = n,
+            "blue" => self.0.b = n,
+            _ => bail!("unknown parameter '{name}', expected red, green or blue"),
+        }
+        Ok(())
+    }
+}
+
 impl Cubes {
     fn power(&self) -> usize {
         self.r * self.g * self.b
@@ -116,6 +153,32 @@ impl FromStr for Cubes {
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn test_bag() {
+        let p = Problem;
+        let input = p.parse_input(EXAMPLE.as_bytes()).unwrap();
+        let solve = |params: &[&str]| {
+            let params = params
+                .iter()
+                .map(|s| s.parse().unwrap())
+                .collect::<Vec<_>>();
+            p.solve_first(&input, &(), &Bag::with(&params).unwrap())
+                .unwrap()
+        };
+
+        assert_eq!(solve(&[]), 8);
+        assert_eq!(solve(&["red=20"]), 11);
+        assert_eq!(solve(&["red=20", "blue=15"]), 15);
+        assert!(Bag::with(&["purple=1".parse().unwrap()]).is_err());
+        assert!(Bag::with(&["red=many".parse().unwrap()]).is_err());
+    }
+
     #[test]
     fn test_cubes() {
         let c = " 1 red, 2 green, 6 blue";
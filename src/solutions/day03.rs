@@ -9,21 +9,37 @@ impl Solver for Problem {
     type Input = Grid<u8>;
     type Output1 = u64;
     type Output2 = u64;
+    type Prepared = ();
+    type Params = ();
 
-    fn parse_input<R: Read>(&self, r: R) -> Self::Input {
-        Grid::from_reader(r).expect("valid grid")
+    fn parse_input<R: Read>(&self, r: R) -> anyhow::Result<Self::Input> {
+        Ok(Grid::from_reader(r)?)
     }
 
-    fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
-        find_correct_numbers(input).iter().sum()
+    fn prepare(&self, _input: &Self::Input) -> anyhow::Result<Self::Prepared> {
+        Ok(())
     }
 
-    fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
-        find_gear_coords_for_numbers(input)
+    fn solve_first(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output1> {
+        Ok(find_correct_numbers(input).iter().sum())
+    }
+
+    fn solve_second(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output2> {
+        Ok(find_gear_coords_for_numbers(input)
             .iter()
             .filter(|(_, v)| v.len() == 2)
             .map(|(_, v)| v[0] * v[1])
-            .sum()
+            .sum())
     }
 }
 
@@ -11,19 +11,34 @@ impl Solver for Problem {
     type Input = Vec<Card>;
     type Output1 = u64;
     type Output2 = usize;
+    type Prepared = ();
+    type Params = ();
 
-    fn parse_input<R: Read>(&self, r: R) -> Self::Input {
+    fn parse_input<R: Read>(&self, r: R) -> anyhow::Result<Self::Input> {
         r.split_lines()
     }
 
-    fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
-        input.iter().map(|c| c.points()).sum()
+    fn prepare(&self, _input: &Self::Input) -> anyhow::Result<Self::Prepared> {
+        Ok(())
     }
 
-    fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
+    fn solve_first(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output1> {
+        Ok(input.iter().map(|c| c.points()).sum())
+    }
+
+    fn solve_second(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output2> {
         let card_points = input
             .iter()
-            .cloned()
             .map(|c| (c.id, c.matching_numbers()))
             .collect::<HashMap<_, _>>();
 
@@ -41,7 +56,7 @@ impl Solver for Problem {
             }
         }
 
-        result.len()
+        Ok(result.len())
     }
 }
 
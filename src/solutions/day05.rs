@@ -13,23 +13,39 @@ impl Solver for Problem {
     type Input = Almanac;
     type Output1 = usize;
     type Output2 = usize;
+    type Prepared = ();
+    type Params = ();
 
-    fn parse_input<R: Read>(&self, r: R) -> Self::Input {
-        Self::Input::from_reader(r).expect("valid input")
+    fn parse_input<R: Read>(&self, r: R) -> anyhow::Result<Self::Input> {
+        Self::Input::from_reader(r)
     }
 
-    fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
-        input
+    fn prepare(&self, _input: &Self::Input) -> anyhow::Result<Self::Prepared> {
+        Ok(())
+    }
+
+    fn solve_first(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output1> {
+        Ok(input
             .seeds
             .iter()
             .cloned()
             .map(|seed| input.maps.iter().fold(seed, |id, map| map.get(id)))
             .min()
-            .unwrap_or(0)
+            .unwrap_or(0))
     }
 
-    fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
-        input
+    fn solve_second(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output2> {
+        Ok(input
             .seed_pairs()
             .into_par_iter()
             .flat_map(|range| {
@@ -38,7 +54,7 @@ impl Solver for Problem {
                     .map(|seed| input.maps.iter().fold(seed, |id, map| map.get(id)))
             })
             .min()
-            .unwrap_or(0)
+            .unwrap_or(0))
     }
 }
 
@@ -50,7 +66,7 @@ pub struct Almanac {
 
 impl Almanac {
     fn from_reader<R: Read>(r: R) -> anyhow::Result<Self> {
-        let groups: Vec<String> = r.split_groups();
+        let groups: Vec<String> = r.split_groups()?;
         let mut group_iter = groups.iter();
 
         let seeds = scan_fmt!(
@@ -92,7 +108,11 @@ impl FromStr for Map {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self {
-            entries: s.lines().skip(1).flat_map(|l| l.parse()).collect(),
+            entries: s
+                .lines()
+                .skip(1)
+                .map(str::parse)
+                .collect::<anyhow::Result<_>>()?,
         })
     }
 }
@@ -107,12 +127,14 @@ impl MapEntry {
     fn new(n: usize, source: usize, dest: usize) -> Self {
         Self {
             range: source..=source + n,
-            delta: dest - source,
+            delta: dest.wrapping_sub(source),
         }
     }
 
     fn get(&self, id: usize) -> Option<usize> {
-        self.range.contains(&id).then_some(self.delta + id)
+        self.range
+            .contains(&id)
+            .then_some(self.delta.wrapping_add(id))
     }
 }
 
@@ -13,21 +13,37 @@ impl Solver for Problem {
     type Input = Races;
     type Output1 = usize;
     type Output2 = usize;
+    type Prepared = ();
+    type Params = ();
 
-    fn parse_input<R: Read>(&self, r: R) -> Self::Input {
-        Races::from_str(&read_to_string(r).unwrap()).unwrap()
+    fn parse_input<R: Read>(&self, r: R) -> anyhow::Result<Self::Input> {
+        Races::from_str(&read_to_string(r)?)
     }
 
-    fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
-        input
+    fn prepare(&self, _input: &Self::Input) -> anyhow::Result<Self::Prepared> {
+        Ok(())
+    }
+
+    fn solve_first(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output1> {
+        Ok(input
             .0
             .iter()
             .map(|r| r.find_times_above_record().len())
-            .product()
+            .product())
     }
 
-    fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
-        input.as_single_race().find_times_above_record_par().len()
+    fn solve_second(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output2> {
+        Ok(input.as_single_race()?.find_times_above_record_par().len())
     }
 }
 
@@ -65,22 +81,15 @@ impl Race {
 pub struct Races(Vec<Race>);
 
 impl Races {
-    fn as_single_race(&self) -> Race {
-        let time = self
-            .0
-            .iter()
-            .map(|r| r.time.to_string())
-            .join("")
-            .parse()
-            .unwrap();
+    fn as_single_race(&self) -> anyhow::Result<Race> {
+        let time = self.0.iter().map(|r| r.time.to_string()).join("").parse()?;
         let distance = self
             .0
             .iter()
             .map(|r| r.distance.to_string())
             .join("")
-            .parse()
-            .unwrap();
-        Race { time, distance }
+            .parse()?;
+        Ok(Race { time, distance })
     }
 }
 
@@ -154,7 +163,7 @@ mod tests {
             },
         ]);
         assert_eq!(
-            races.as_single_race(),
+            races.as_single_race().unwrap(),
             Race {
                 time: 71530,
                 distance: 940200
@@ -1,7 +1,9 @@
 use crate::parsing::ReadExt;
 use crate::solver::Solver;
+use scan_fmt::scan_fmt;
 use std::collections::BTreeSet;
-use std::io::{read_to_string, Read};
+use std::io::Read;
+use std::str::FromStr;
 
 pub struct Problem;
 
@@ -9,36 +11,74 @@ impl Solver for Problem {
     type Input = (BTreeSet<part1::HandBid>, BTreeSet<part2::HandBid>);
     type Output1 = u64;
     type Output2 = u64;
+    type Prepared = ();
+    type Params = ();
 
-    fn parse_input<R: Read>(&self, r: R) -> Self::Input {
-        let s = read_to_string(r).unwrap();
-        (s.as_bytes().split_lines(), s.as_bytes().split_lines())
+    fn parse_input<R: Read>(&self, r: R) -> anyhow::Result<Self::Input> {
+        // lines are scanned once, each part then ranks the cards its own way
+        let lines: Vec<Line> = r.split_lines()?;
+        let first = lines
+            .iter()
+            .map(|l| part1::HandBid::new(&l.hand, l.bid))
+            .collect::<anyhow::Result<_>>()?;
+        let second = lines
+            .iter()
+            .map(|l| part2::HandBid::new(&l.hand, l.bid))
+            .collect::<anyhow::Result<_>>()?;
+        Ok((first, second))
+    }
+
+    fn prepare(&self, _input: &Self::Input) -> anyhow::Result<Self::Prepared> {
+        Ok(())
     }
 
-    fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
-        input
+    fn solve_first(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output1> {
+        Ok(input
             .0
             .iter()
             .enumerate()
             .map(|(rank, h)| (rank as u64 + 1) * h.bid)
-            .sum()
+            .sum())
     }
 
-    fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
+    fn solve_second(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output2> {
         //input.1.iter().for_each(|c| println!("{:?}", c));
-        input
+        Ok(input
             .1
             .iter()
             .enumerate()
             .map(|(rank, h)| (rank as u64 + 1) * h.bid)
-            .sum()
+            .sum())
+    }
+}
+
+struct Line {
+    hand: String,
+    bid: u64,
+}
+
+impl FromStr for Line {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hand, bid) = scan_fmt!(s, "{} {d}", String, u64)?;
+        Ok(Self { hand, bid })
     }
 }
 
 mod part1 {
     use anyhow::anyhow;
     use itertools::Itertools;
-    use scan_fmt::scan_fmt;
     use std::cmp::Ordering;
     use std::collections::HashMap;
     use std::str::FromStr;
@@ -151,11 +191,8 @@ mod part1 {
         }
     }
 
-    impl FromStr for HandBid {
-        type Err = anyhow::Error;
-
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let (hand, bid) = scan_fmt!(s, "{} {d}", String, u64)?;
+    impl HandBid {
+        pub fn new(hand: &str, bid: u64) -> anyhow::Result<Self> {
             Ok(Self {
                 hand: Hand::new(
                     hand.split("")
@@ -225,10 +262,12 @@ mod part1 {
         use super::*;
         use crate::solutions::day07::part1::Card::*;
         use crate::solutions::day07::part1::Combination::*;
+        use crate::solutions::day07::Line;
 
         #[test]
-        fn from_str() {
-            let hb = HandBid::from_str("32T3K 765").unwrap();
+        fn from_line() {
+            let l = "32T3K 765".parse::<Line>().unwrap();
+            let hb = HandBid::new(&l.hand, l.bid).unwrap();
             assert_eq!(
                 hb,
                 HandBid {
@@ -286,7 +325,6 @@ mod part1 {
 mod part2 {
     use anyhow::anyhow;
     use itertools::Itertools;
-    use scan_fmt::scan_fmt;
     use std::cmp::Ordering;
     use std::collections::HashMap;
     use std::str::FromStr;
@@ -418,11 +456,8 @@ mod part2 {
         }
     }
 
-    impl FromStr for HandBid {
-        type Err = anyhow::Error;
-
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let (hand, bid) = scan_fmt!(s, "{} {d}", String, u64)?;
+    impl HandBid {
+        pub fn new(hand: &str, bid: u64) -> anyhow::Result<Self> {
             Ok(Self {
                 hand: Hand::new(
                     hand.split("")
@@ -492,10 +527,12 @@ mod part2 {
         use super::*;
         use crate::solutions::day07::part2::Card::*;
         use crate::solutions::day07::part2::Combination::*;
+        use crate::solutions::day07::Line;
 
         #[test]
-        fn from_str() {
-            let hb = HandBid::from_str("32T3K 765").unwrap();
+        fn from_line() {
+            let l = "32T3K 765".parse::<Line>().unwrap();
+            let hb = HandBid::new(&l.hand, l.bid).unwrap();
             assert_eq!(
                 hb,
                 HandBid {
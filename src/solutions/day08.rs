@@ -1,5 +1,6 @@
+use crate::params::Params;
 use crate::solver::Solver;
-use anyhow::anyhow;
+use anyhow::{anyhow, bail, Context};
 use itertools::Itertools;
 use rayon::prelude::*;
 use scan_fmt::scan_fmt;
@@ -14,29 +15,44 @@ impl Solver for Problem {
     type Input = Map;
     type Output1 = usize;
     type Output2 = usize;
+    type Prepared = ();
+    type Params = Endpoints;
 
-    fn parse_input<R: Read>(&self, r: R) -> Self::Input {
+    fn parse_input<R: Read>(&self, r: R) -> anyhow::Result<Self::Input> {
         let mut r = BufReader::new(r).lines();
         let directions = r
             .next()
-            .unwrap()
-            .unwrap()
-            .split("")
-            .flat_map(Dir::from_str)
-            .collect();
+            .ok_or(anyhow!("missing directions"))??
+            .split_terminator("")
+            .skip(1)
+            .map(Dir::from_str)
+            .collect::<anyhow::Result<_>>()
+            .context("line 1")?;
 
         let graph = r
+            .enumerate()
             .skip(1)
-            .flatten()
-            .flat_map(|s| scan_fmt!(&s, "{} = ({}, {})", String, String, String))
-            .map(|(a, b, c)| (node_to_u16(&a), (node_to_u16(&b), node_to_u16(&c))))
-            .collect();
+            .map(|(i, l)| {
+                let (a, b, c) = scan_fmt!(&l?, "{} = ({}, {})", String, String, String)
+                    .with_context(|| format!("line {}", i + 2))?;
+                Ok((node_to_u16(&a), (node_to_u16(&b), node_to_u16(&c))))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Map { directions, graph })
+    }
 
-        Map { directions, graph }
+    fn prepare(&self, _input: &Self::Input) -> anyhow::Result<Self::Prepared> {
+        Ok(())
     }
 
-    fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
-        let mut current_node = AAA;
+    fn solve_first(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        params: &Self::Params,
+    ) -> anyhow::Result<Self::Output1> {
+        let mut current_node = params.start;
         for (step, dir) in input.directions.iter().cycle().enumerate() {
             current_node = input
                 .graph
@@ -45,16 +61,21 @@ impl Solver for Problem {
                     Dir::Left => l,
                     Dir::Right => r,
                 })
-                .unwrap();
-            if current_node == ZZZ {
-                return step + 1;
+                .ok_or(anyhow!("unknown node {current_node}"))?;
+            if current_node == params.end {
+                return Ok(step + 1);
             }
         }
 
-        unreachable!()
+        Err(anyhow!("missing directions"))
     }
 
-    fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
+    fn solve_second(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output2> {
         let mut current_nodes = input
             .graph
             .keys()
@@ -91,23 +112,53 @@ impl Solver for Problem {
                     .cloned()
                     .reduce(lcm)
                 {
-                    return lcm;
+                    return Ok(lcm);
                 }
             }
 
             // easy way out
             if current_nodes.iter().all(|&e| is_ending_node(e)) {
-                return step + 1;
+                return Ok(step + 1);
             }
         }
 
-        unreachable!()
+        Err(anyhow!("missing directions"))
     }
 }
 
 const AAA: u16 = 0;
 const ZZZ: u16 = 26 * 26 * 26 - 1;
 
+/// Nodes of the first part, set with `--param start=AAA` and `--param end=ZZZ`.
+pub struct Endpoints {
+    start: u16,
+    end: u16,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            start: AAA,
+            end: ZZZ,
+        }
+    }
+}
+
+impl Params for Endpoints {
+    fn set(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        let node = match name {
+            "start" => &mut self.start,
+            "end" => &mut self.end,
+            _ => bail!("unknown parameter '{name}', expected start or end"),
+        };
+        if value.len() != 3 || !value.bytes().all(|b| b.is_ascii_uppercase()) {
+            bail!("invalid node '{value}'");
+        }
+        *node = node_to_u16(value);
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub struct Map {
     directions: Vec<Dir>,
@@ -137,11 +188,11 @@ fn node_to_u16(n: &str) -> u16 {
 }
 
 fn is_starter_node(v: u16) -> bool {
-    v % 26 == 0
+    v.is_multiple_of(26)
 }
 
 fn is_ending_node(v: u16) -> bool {
-    (v + 1) % 26 == 0
+    (v + 1).is_multiple_of(26)
 }
 
 fn lcm(first: usize, second: usize) -> usize {
@@ -170,6 +221,33 @@ fn gcd(first: usize, second: usize) -> usize {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_endpoints() {
+        let i = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)"
+            .as_bytes();
+        let p = Problem;
+        let input = p.parse_input(i).unwrap();
+        let solve = |params: &[&str]| {
+            let params = params
+                .iter()
+                .map(|s| s.parse().unwrap())
+                .collect::<Vec<_>>();
+            p.solve_first(&input, &(), &Endpoints::with(&params).unwrap())
+                .unwrap()
+        };
+
+        assert_eq!(solve(&[]), 6);
+        assert_eq!(solve(&["start=BBB"]), 3);
+        assert_eq!(solve(&["start=BBB", "end=AAA"]), 1);
+        assert!(Endpoints::with(&["start=AA".parse().unwrap()]).is_err());
+        assert!(Endpoints::with(&["start=aaa".parse().unwrap()]).is_err());
+        assert!(Endpoints::with(&["stop=ZZZ".parse().unwrap()]).is_err());
+    }
+
     #[test]
     fn test_node_to_u16() {
         assert_eq!(node_to_u16("AAA"), 0);
@@ -1,10 +1,9 @@
-use crate::parsing::WhitespaceSeparatedList;
+use crate::parsing::{ReadExt, WhitespaceSeparatedList};
 use crate::solver::Solver;
 use itertools::Itertools;
 use std::collections::VecDeque;
-use std::io::{BufRead, BufReader, Read};
+use std::io::Read;
 use std::iter::successors;
-use std::str::FromStr;
 
 pub struct Problem;
 
@@ -12,27 +11,39 @@ impl Solver for Problem {
     type Input = Vec<Vec<i64>>;
     type Output1 = i64;
     type Output2 = i64;
+    type Prepared = ();
+    type Params = ();
 
-    fn parse_input<R: Read>(&self, r: R) -> Self::Input {
-        BufReader::new(r)
-            .lines()
-            .flatten()
-            .flat_map(|l| WhitespaceSeparatedList::from_str(&l))
-            .map(|l| l.into())
-            .collect()
+    fn parse_input<R: Read>(&self, r: R) -> anyhow::Result<Self::Input> {
+        let lines: Vec<WhitespaceSeparatedList<i64>> = r.split_lines()?;
+        Ok(lines.into_iter().map(Vec::from).collect())
     }
 
-    fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
-        input.iter().flat_map(|v| extrapolate(v).pop()).sum()
+    fn prepare(&self, _input: &Self::Input) -> anyhow::Result<Self::Prepared> {
+        Ok(())
     }
 
-    fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
+    fn solve_first(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output1> {
+        Ok(input.iter().flat_map(|v| extrapolate(v).pop()).sum())
+    }
+
+    fn solve_second(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output2> {
         let mut reversed = input.clone();
         for v in &mut reversed {
             v.reverse();
         }
 
-        reversed.iter().flat_map(|v| extrapolate(v).pop()).sum()
+        Ok(reversed.iter().flat_map(|v| extrapolate(v).pop()).sum())
     }
 }
 
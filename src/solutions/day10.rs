@@ -11,60 +11,34 @@ impl Solver for Problem {
     type Input = Grid<Tile>;
     type Output1 = usize;
     type Output2 = usize;
+    type Prepared = Loop;
+    type Params = ();
 
-    fn parse_input<R: Read>(&self, r: R) -> Self::Input {
-        Grid::from_reader(r).unwrap()
+    fn parse_input<R: Read>(&self, r: R) -> anyhow::Result<Self::Input> {
+        Grid::from_reader(r)
     }
 
-    fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
-        //println!("{input}");
-
-        let start = find_start(input).unwrap();
-        let loop_starts = find_loop_starts(input, start);
-        let mut previous_coord = start;
-        let mut current_coord = *loop_starts.first().unwrap();
-        let mut len = 1usize;
-        let end = *loop_starts.last().unwrap();
-
-        while current_coord != end {
-            let new_current_coord = find_next_step(input, current_coord, previous_coord).unwrap();
-            previous_coord = current_coord;
-            current_coord = new_current_coord;
-            len += 1;
-        }
-
-        (len + 1) / 2
+    /// Walks the loop once, both parts only need the pipes it's made of.
+    fn prepare(&self, input: &Self::Input) -> anyhow::Result<Self::Prepared> {
+        find_path(input).map(Loop)
     }
 
-    fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
-        let mut path = HashMap::new();
-
-        // find all coords representing the path
-        let start = find_start(input).unwrap();
-        let loop_starts = find_loop_starts(input, start);
-        let mut previous_coord = start;
-        let mut current_coord = *loop_starts.first().unwrap();
-        let end = *loop_starts.last().unwrap();
-
-        // insert start as pipe
-        let p1 = Pos::new(start, current_coord).unwrap();
-        let p2 = Pos::new(start, end).unwrap();
-        let pipe = Pipe::from_pos(p1, p2).unwrap();
-        path.insert(start, pipe);
-
-        while current_coord != end {
-            if let Some(p) = input.get(current_coord).unwrap().as_pipe() {
-                path.insert(current_coord, *p);
-            }
-
-            let new_current_coord = find_next_step(input, current_coord, previous_coord).unwrap();
-            previous_coord = current_coord;
-            current_coord = new_current_coord;
-        }
-
-        // insert end as pipe
-        path.insert(end, *input.get(end).unwrap().as_pipe().unwrap());
+    fn solve_first(
+        &self,
+        _input: &Self::Input,
+        path: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output1> {
+        Ok(path.0.len() / 2)
+    }
 
+    fn solve_second(
+        &self,
+        input: &Self::Input,
+        path: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output2> {
+        let path = &path.0;
         let mut output = Grid::new_with(input.w, input.h, '.');
 
         // for each point not in the path, trace a ray right to count intersections with the path
@@ -73,7 +47,7 @@ impl Solver for Problem {
         let count = input
             .iter_with_coords()
             .filter_map(|(c, _)| (!path.contains_key(&c)).then_some(c))
-            .map(|c| (c, trace_ray_count_intersections(c, input, &path)))
+            .map(|c| (c, trace_ray_count_intersections(c, input, path)))
             .filter(|(_, n)| n % 2 != 0)
             .inspect(|(c, _)| {
                 if let Some(e) = output.get_mut(c) {
@@ -84,10 +58,51 @@ impl Solver for Problem {
 
         //println!("{output}");
 
-        count
+        Ok(count)
     }
 }
 
+/// Pipes of the loop by coords, start included.
+pub struct Loop(HashMap<(usize, usize), Pipe>);
+
+/// Finds all coords representing the path.
+fn find_path(input: &Grid<Tile>) -> anyhow::Result<HashMap<(usize, usize), Pipe>> {
+    let mut path = HashMap::new();
+
+    let start = find_start(input).ok_or(anyhow!("missing start tile"))?;
+    let loop_starts = find_loop_starts(input, start);
+    let mut previous_coord = start;
+    let mut current_coord = *loop_starts.first().ok_or(anyhow!("no loop from start"))?;
+    let end = *loop_starts.last().ok_or(anyhow!("no loop from start"))?;
+
+    // insert start as pipe
+    let pipe = Pos::new(start, current_coord)
+        .zip(Pos::new(start, end))
+        .and_then(|(p1, p2)| Pipe::from_pos(p1, p2))
+        .ok_or(anyhow!("no pipe fits the start tile"))?;
+    path.insert(start, pipe);
+
+    while current_coord != end {
+        if let Some(p) = input.get(current_coord).and_then(Tile::as_pipe) {
+            path.insert(current_coord, *p);
+        }
+
+        let new_current_coord = find_next_step(input, current_coord, previous_coord)
+            .ok_or(anyhow!("broken loop at {current_coord:?}"))?;
+        previous_coord = current_coord;
+        current_coord = new_current_coord;
+    }
+
+    // insert end as pipe
+    let end_pipe = input
+        .get(end)
+        .and_then(Tile::as_pipe)
+        .ok_or(anyhow!("loop end is not a pipe"))?;
+    path.insert(end, *end_pipe);
+
+    Ok(path)
+}
+
 fn trace_ray_count_intersections(
     (from_x, from_y): (usize, usize),
     grid: &Grid<Tile>,
@@ -160,7 +175,7 @@ fn find_next_step(
         .find(|(c, t)| {
             let pos = Pos::new(current, *c);
             match (pos, t) {
-                (Some(pos), Tile::Pipe(pipe)) => current_pipe.is_compatible_with(&pipe, &pos),
+                (Some(pos), Tile::Pipe(pipe)) => current_pipe.is_compatible_with(pipe, &pos),
                 _ => false,
             }
         })
@@ -336,3 +351,22 @@ impl TryFrom<u8> for Pipe {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_first() {
+        let i = ".....
+.S-7.
+.|.|.
+.L-J.
+....."
+            .as_bytes();
+        let p = Problem;
+        let input = p.parse_input(i).unwrap();
+        let path = p.prepare(&input).unwrap();
+        assert_eq!(p.solve_first(&input, &path, &()).unwrap(), 4);
+    }
+}
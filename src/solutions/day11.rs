@@ -1,6 +1,7 @@
 use crate::grid::Grid;
+use crate::params::Params;
 use crate::solver::Solver;
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use itertools::Itertools;
 use std::io::Read;
 
@@ -10,17 +11,58 @@ impl Solver for Problem {
     type Input = Grid<Pixel>;
     type Output1 = u64;
     type Output2 = u64;
+    type Prepared = ();
+    type Params = Expansion;
 
-    fn parse_input<R: Read>(&self, r: R) -> Self::Input {
-        Grid::from_reader(r).unwrap()
+    fn parse_input<R: Read>(&self, r: R) -> anyhow::Result<Self::Input> {
+        Grid::from_reader(r)
     }
 
-    fn solve_first(&self, input: &Self::Input) -> Self::Output1 {
-        sum_of_distances(input, 1)
+    fn prepare(&self, _input: &Self::Input) -> anyhow::Result<Self::Prepared> {
+        Ok(())
     }
 
-    fn solve_second(&self, input: &Self::Input) -> Self::Output2 {
-        sum_of_distances(input, 999999)
+    fn solve_first(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        _params: &Self::Params,
+    ) -> anyhow::Result<Self::Output1> {
+        Ok(sum_of_distances(input, 1))
+    }
+
+    fn solve_second(
+        &self,
+        input: &Self::Input,
+        _prepared: &Self::Prepared,
+        params: &Self::Params,
+    ) -> anyhow::Result<Self::Output2> {
+        Ok(sum_of_distances(input, params.expand - 1))
+    }
+}
+
+/// How many rows or columns each empty one becomes in the second part,
+/// set with `--param expand=10`.
+pub struct Expansion {
+    expand: usize,
+}
+
+impl Default for Expansion {
+    fn default() -> Self {
+        Self { expand: 1000000 }
+    }
+}
+
+impl Params for Expansion {
+    fn set(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        match name {
+            "expand" => self.expand = value.parse()?,
+            _ => bail!("unknown parameter '{name}', expected expand"),
+        }
+        if self.expand == 0 {
+            bail!("empty space can't vanish");
+        }
+        Ok(())
     }
 }
 
@@ -75,13 +117,7 @@ fn sum_of_distances(g: &Grid<Pixel>, expand_by: usize) -> u64 {
         .iter()
         .combinations(2)
         .map(|v| {
-            expanding_manhattan_distance(
-                **v.get(0).unwrap(),
-                **v.get(1).unwrap(),
-                &empty_cols,
-                &empty_rows,
-                expand_by,
-            ) as u64
+            expanding_manhattan_distance(*v[0], *v[1], &empty_cols, &empty_rows, expand_by) as u64
         })
         .sum()
 }
@@ -103,3 +139,37 @@ impl TryFrom<u8> for Pixel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expansion() {
+        let i = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#....."
+            .as_bytes();
+        let p = Problem;
+        let input = p.parse_input(i).unwrap();
+        let solve = |expand: &str| {
+            let params = Expansion::with(&[format!("expand={expand}").parse().unwrap()]).unwrap();
+            p.solve_second(&input, &(), &params).unwrap()
+        };
+
+        assert_eq!(
+            p.solve_first(&input, &(), &Expansion::default()).unwrap(),
+            374
+        );
+        assert_eq!(solve("10"), 1030);
+        assert_eq!(solve("100"), 8410);
+        assert!(Expansion::with(&["expand=0".parse().unwrap()]).is_err());
+    }
+}
@@ -1,47 +1,499 @@
+use crate::alloc::{track, AllocStats};
+use crate::answer::Answer;
+use crate::input::InputSource;
+use crate::params::{Param, Params};
+use crate::solutions::SOLVERS;
+use anyhow::{anyhow, Context};
 use humantime::format_duration;
-use std::fmt::Display;
-use std::fs::File;
-use std::io;
+use rayon::ThreadPoolBuilder;
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
 use std::io::Read;
-use std::path::Path;
-use std::time::Instant;
-
-fn input_file(day: u32) -> String {
-    format!("input/{:02}.txt", day)
-}
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Once};
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub trait Solver {
     type Input;
-    type Output1: Display;
-    type Output2: Display;
+    type Output1: Into<Answer>;
+    type Output2: Into<Answer>;
+    /// Work shared by both parts, `()` for solvers without any.
+    type Prepared: Send + Sync + 'static;
+    /// `()` for solvers without puzzle constants.
+    type Params: Params;
+
+    fn parse_input<R: Read>(&self, r: R) -> anyhow::Result<Self::Input>;
+    /// Does the work shared by both parts, such as a search both answers come from,
+    /// once after parsing. Solvers without any return `Ok(())`.
+    fn prepare(&self, input: &Self::Input) -> anyhow::Result<Self::Prepared>;
+    fn solve_first(
+        &self,
+        input: &Self::Input,
+        prepared: &Self::Prepared,
+        params: &Self::Params,
+    ) -> anyhow::Result<Self::Output1>;
+    fn solve_second(
+        &self,
+        input: &Self::Input,
+        prepared: &Self::Prepared,
+        params: &Self::Params,
+    ) -> anyhow::Result<Self::Output2>;
+
+    fn load_input(&self, source: &InputSource) -> anyhow::Result<Self::Input> {
+        let r = source.open().with_context(|| match source {
+            // solved days are registered whether their input is there or not
+            InputSource::File(p) if !p.exists() => format!("missing input {source}"),
+            _ => format!("unable to open {source}"),
+        })?;
+        catch_panics(|| self.parse_input(r)).with_context(|| format!("unable to parse {source}"))
+    }
+}
+
+/// A parsed input, along with the parameters, or a prepared value,
+/// whose type is only known to its solver.
+pub type AnyInput = dyn Any + Send + Sync;
+
+/// Object-safe view of a [`Solver`], with a type-erased input and [`Answer`]s,
+/// so that solvers of every day fit in a single registry.
+pub trait DynSolver: Send + Sync {
+    fn parse(&self, source: &InputSource, params: &[Param]) -> anyhow::Result<Box<AnyInput>>;
+    /// Whether the solver shares work between parts, its time then being reported.
+    fn prepares(&self) -> bool;
+    fn prepare(&self, input: &AnyInput) -> anyhow::Result<Box<AnyInput>>;
+    fn solve_first(&self, input: &AnyInput, prepared: &AnyInput) -> anyhow::Result<Answer>;
+    fn solve_second(&self, input: &AnyInput, prepared: &AnyInput) -> anyhow::Result<Answer>;
+}
+
+impl<S> DynSolver for S
+where
+    S: Solver + Send + Sync,
+    S::Input: Send + Sync + 'static,
+{
+    fn parse(&self, source: &InputSource, params: &[Param]) -> anyhow::Result<Box<AnyInput>> {
+        let params = S::Params::with(params)?;
+        Ok(Box::new((self.load_input(source)?, params)))
+    }
+
+    fn prepares(&self) -> bool {
+        TypeId::of::<S::Prepared>() != TypeId::of::<()>()
+    }
+
+    fn prepare(&self, input: &AnyInput) -> anyhow::Result<Box<AnyInput>> {
+        let (input, _) = downcast::<(S::Input, S::Params)>(input)?;
+        Ok(Box::new(Solver::prepare(self, input)?))
+    }
+
+    fn solve_first(&self, input: &AnyInput, prepared: &AnyInput) -> anyhow::Result<Answer> {
+        let (input, params) = downcast::<(S::Input, S::Params)>(input)?;
+        let prepared = downcast::<S::Prepared>(prepared)?;
+        Ok(Solver::solve_first(self, input, prepared, params)?.into())
+    }
+
+    fn solve_second(&self, input: &AnyInput, prepared: &AnyInput) -> anyhow::Result<Answer> {
+        let (input, params) = downcast::<(S::Input, S::Params)>(input)?;
+        let prepared = downcast::<S::Prepared>(prepared)?;
+        Ok(Solver::solve_second(self, input, prepared, params)?.into())
+    }
+}
+
+fn downcast<T: 'static>(input: &AnyInput) -> anyhow::Result<&T> {
+    input
+        .downcast_ref()
+        .ok_or(anyhow!("input parsed by another solver"))
+}
+
+/// Days having a solver, in order.
+pub fn solved_days() -> Vec<u32> {
+    SOLVERS.iter().map(|(day, _)| *day).collect()
+}
+
+pub fn solver_for(day: u32) -> Option<&'static dyn DynSolver> {
+    SOLVERS
+        .iter()
+        .find(|(d, _)| *d == day)
+        .map(|(_, solver)| solver.as_ref())
+}
+
+/// Solves the selected parts. Failing parts, including panicking ones,
+/// are reported in their result, only a failed parsing or preparation is an error.
+///
+/// With a timeout, the prepare stage and the parts run on their own thread
+/// which is left behind once the time budget is spent, hence the `'static` solver.
+/// With a number of threads, each phase gets its own rayon pool.
+pub fn solve(
+    solver: &'static dyn DynSolver,
+    source: &InputSource,
+    params: &[Param],
+    options: SolveOptions,
+) -> anyhow::Result<DayResult> {
+    let ((input, parse_time), parse_allocs) = in_pool(options.threads, || {
+        track(|| {
+            let start = Instant::now();
+            let input = solver.parse(source, params);
+            (input, start.elapsed())
+        })
+    })?;
+    let input = Arc::<AnyInput>::from(input?);
+
+    let (prepared, prepare_time, prepare_allocs) = if solver.prepares() {
+        let input = input.clone();
+        let (prepared, time, allocs) = run_phase(options, move || solver.prepare(&*input));
+        (prepared, Some(time), allocs)
+    } else {
+        (solver.prepare(&*input), None, None)
+    };
+    let prepared =
+        Arc::<AnyInput>::from(prepared.with_context(|| format!("unable to prepare {source}"))?);
+
+    let first = options.parts.includes(1).then(|| {
+        let (input, prepared) = (input.clone(), prepared.clone());
+        PartResult::new(options, move || solver.solve_first(&*input, &*prepared))
+    });
+    let second = options.parts.includes(2).then(|| {
+        let (input, prepared) = (input.clone(), prepared.clone());
+        PartResult::new(options, move || solver.solve_second(&*input, &*prepared))
+    });
+
+    Ok(DayResult {
+        parse_time,
+        parse_allocs,
+        prepare_time,
+        prepare_allocs,
+        first,
+        second,
+    })
+}
+
+/// Which parts of a day to solve, parsing always happens.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Parts {
+    #[default]
+    Both,
+    Only(u8),
+}
+
+impl Parts {
+    pub fn includes(self, part: u8) -> bool {
+        match self {
+            Self::Both => true,
+            Self::Only(p) => p == part,
+        }
+    }
+
+    /// The parts included in both, `None` if there are none.
+    pub fn intersect(self, other: Self) -> Option<Self> {
+        match (self, other) {
+            (Self::Both, p) | (p, Self::Both) => Some(p),
+            (Self::Only(a), Self::Only(b)) => (a == b).then_some(self),
+        }
+    }
+}
+
+impl From<Option<u8>> for Parts {
+    fn from(part: Option<u8>) -> Self {
+        part.map_or(Self::Both, Self::Only)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SolveOptions {
+    pub parts: Parts,
+    /// Time budget of each part, unlimited by default.
+    pub timeout: Option<Duration>,
+    /// Size of the rayon pool, the global pool using every CPU by default.
+    pub threads: Option<usize>,
+}
+
+/// Runs `f` on a new pool of `threads` threads, or on the current thread
+/// which uses the global pool.
+fn in_pool<T: Send>(threads: Option<usize>, f: impl FnOnce() -> T + Send) -> anyhow::Result<T> {
+    let Some(threads) = threads else {
+        return Ok(f());
+    };
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .context("unable to build the thread pool")?;
+    Ok(pool.install(f))
+}
+
+/// Why a phase stopped without returning, `Unsolved` being a `todo!()`.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Abort {
+    Unsolved,
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+impl Abort {
+    fn from_payload(payload: Box<dyn Any + Send>) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+
+        if message.starts_with("not yet implemented") {
+            Self::Unsolved
+        } else {
+            Self::Panicked(message)
+        }
+    }
+}
+
+impl Display for Abort {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsolved => write!(f, "unsolved"),
+            Self::Panicked(message) => write!(f, "panicked: {message}"),
+            Self::TimedOut(t) => write!(f, "timed out after {}", format_duration(*t)),
+        }
+    }
+}
+
+impl std::error::Error for Abort {}
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs a phase of a solution, turning its panics into [`Abort`] errors
+/// instead of unwinding through the runner.
+pub fn catch_panics<T>(f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        // caught panics are reported with the results, not on stderr
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.get() {
+                default(info);
+            }
+        }));
+    });
+
+    let catching = CATCHING.replace(true);
+    let r = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(catching);
+    r.unwrap_or_else(|payload| Err(Abort::from_payload(payload).into()))
+}
+
+#[derive(Debug)]
+pub struct PartResult {
+    pub answer: anyhow::Result<Answer>,
+    pub time: Duration,
+    /// Only counted with the `count-allocs` feature.
+    pub allocs: Option<AllocStats>,
+}
 
-    fn parse_input<R: Read>(&self, r: R) -> Self::Input;
-    fn solve_first(&self, input: &Self::Input) -> Self::Output1;
-    fn solve_second(&self, input: &Self::Input) -> Self::Output2;
+/// What a phase returned, with its time and allocations.
+type Timed<T> = (anyhow::Result<T>, Duration, Option<AllocStats>);
 
-    fn load_input<P: AsRef<Path>>(&self, p: P) -> io::Result<Self::Input> {
-        let f = File::open(p)?;
-        Ok(self.parse_input(f))
+/// Runs a phase on its pool, within its time budget if any.
+fn run_phase<T, F>(options: SolveOptions, f: F) -> Timed<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+{
+    let Some(timeout) = options.timeout else {
+        return timed(options.threads, f);
+    };
+
+    // the watchdog can't stop the phase, it keeps running in the background
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || tx.send(timed(options.threads, f)));
+    rx.recv_timeout(timeout)
+        .unwrap_or_else(|_| (Err(Abort::TimedOut(timeout).into()), timeout, None))
+}
+
+fn timed<T: Send>(
+    threads: Option<usize>,
+    f: impl FnOnce() -> anyhow::Result<T> + Send,
+) -> Timed<T> {
+    let r = in_pool(threads, || {
+        let ((r, time), allocs) = track(|| {
+            let start = Instant::now();
+            let r = catch_panics(f);
+            (r, start.elapsed())
+        });
+        (r, time, allocs)
+    });
+    r.unwrap_or_else(|e| (Err(e), Duration::ZERO, None))
+}
+
+impl PartResult {
+    fn new<F>(options: SolveOptions, f: F) -> Self
+    where
+        F: FnOnce() -> anyhow::Result<Answer> + Send + 'static,
+    {
+        let (answer, time, allocs) = run_phase(options, f);
+        Self {
+            answer,
+            time,
+            allocs,
+        }
+    }
+
+    /// `solved`, `unsolved`, `panicked`, `timed_out` or `error`.
+    pub fn status(&self) -> &'static str {
+        match &self.answer {
+            Ok(_) => "solved",
+            Err(e) => match e.downcast_ref::<Abort>() {
+                Some(Abort::Unsolved) => "unsolved",
+                Some(Abort::Panicked(_)) => "panicked",
+                Some(Abort::TimedOut(_)) => "timed_out",
+                None => "error",
+            },
+        }
+    }
+
+    /// The answer, or why there is none.
+    pub fn summary(&self) -> String {
+        match &self.answer {
+            Ok(answer) => answer.to_string(),
+            Err(e) if e.is::<Abort>() => e.to_string(),
+            Err(e) => format!("Error: {:#}", e),
+        }
     }
+}
 
-    fn solve(&self, day: u32) {
-        let input_file = input_file(day);
+#[derive(Debug)]
+pub struct DayResult {
+    pub parse_time: Duration,
+    pub parse_allocs: Option<AllocStats>,
+    /// `None` when the solver shares no work between parts.
+    pub prepare_time: Option<Duration>,
+    pub prepare_allocs: Option<AllocStats>,
+    pub first: Option<PartResult>,
+    pub second: Option<PartResult>,
+}
 
-        let start = Instant::now();
-        let input = self
-            .load_input(input_file)
-            .expect("unable to open input file");
-        let time = start.elapsed();
-        println!("Parsing: ({})", format_duration(time));
+impl DayResult {
+    pub fn parts(&self) -> impl Iterator<Item = (u8, &PartResult)> {
+        [(1, &self.first), (2, &self.second)]
+            .into_iter()
+            .filter_map(|(n, p)| p.as_ref().map(|p| (n, p)))
+    }
+}
+
+impl Display for DayResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Parsing: ({})",
+            timing(self.parse_time, self.parse_allocs)
+        )?;
+        if let Some(t) = self.prepare_time {
+            write!(f, "\nPreparing: ({})", timing(t, self.prepare_allocs))?;
+        }
+        for (n, p) in self.parts() {
+            match &p.answer {
+                Ok(answer) if answer.is_multi_line() => write!(
+                    f,
+                    "\nSolution {}: ({})\n{}",
+                    n,
+                    timing(p.time, p.allocs),
+                    answer
+                )?,
+                Ok(answer) => write!(
+                    f,
+                    "\nSolution {}: {:<20} ({})",
+                    n,
+                    answer,
+                    timing(p.time, p.allocs)
+                )?,
+                Err(_) => write!(f, "\nSolution {}: {}", n, p.summary())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A duration, followed by the allocations when they are counted.
+pub fn timing(time: Duration, allocs: Option<AllocStats>) -> String {
+    match allocs {
+        Some(allocs) => format!("{}, {allocs}", format_duration(time)),
+        None => format_duration(time).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn caught_panics() {
+        let unsolved = catch_panics::<()>(|| todo!()).unwrap_err();
+        assert_eq!(unsolved.downcast_ref(), Some(&Abort::Unsolved));
+
+        let panicked = catch_panics::<()>(|| panic!("at the {}", "disco")).unwrap_err();
+        assert_eq!(panicked.to_string(), "panicked: at the disco");
+
+        let failed = catch_panics::<()>(|| Err(anyhow!("no loop"))).unwrap_err();
+        assert!(!failed.is::<Abort>());
+
+        assert_eq!(catch_panics(|| Ok(42)).unwrap(), 42);
+    }
+
+    #[test]
+    fn timed_out_part() {
+        let options = SolveOptions {
+            timeout: Some(Duration::from_millis(10)),
+            ..Default::default()
+        };
+
+        let r = PartResult::new(options, || {
+            thread::sleep(Duration::from_secs(1));
+            Ok("late".into())
+        });
+        assert_eq!(r.summary(), "timed out after 10ms");
+
+        let r = PartResult::new(options, || Ok("early".into()));
+        assert_eq!(r.summary(), "early");
+    }
+
+    struct SlowPrepare;
+
+    impl Solver for SlowPrepare {
+        type Input = ();
+        type Output1 = u64;
+        type Output2 = u64;
+        type Prepared = Duration;
+        type Params = ();
+
+        fn parse_input<R: Read>(&self, _r: R) -> anyhow::Result<Self::Input> {
+            Ok(())
+        }
+
+        fn prepare(&self, _input: &Self::Input) -> anyhow::Result<Self::Prepared> {
+            thread::sleep(Duration::from_secs(1));
+            Ok(Duration::ZERO)
+        }
+
+        fn solve_first(&self, _: &(), _: &Duration, _: &()) -> anyhow::Result<Self::Output1> {
+            Ok(1)
+        }
+
+        fn solve_second(&self, _: &(), _: &Duration, _: &()) -> anyhow::Result<Self::Output2> {
+            Ok(2)
+        }
+    }
 
-        let start = Instant::now();
-        let s1 = self.solve_first(&input);
-        let time = start.elapsed();
-        println!("Solution 1: {:<20} ({})", s1, format_duration(time));
+    #[test]
+    fn timed_out_prepare() {
+        static SOLVER: SlowPrepare = SlowPrepare;
+        let options = SolveOptions {
+            timeout: Some(Duration::from_millis(10)),
+            ..Default::default()
+        };
 
-        let start = Instant::now();
-        let s2 = self.solve_second(&input);
-        let time = start.elapsed();
-        println!("Solution 2: {:<20} ({})", s2, format_duration(time));
+        let source = InputSource::File(file!().into());
+        let e = solve(&SOLVER, &source, &[], options).unwrap_err();
+        assert_eq!(
+            e.downcast_ref(),
+            Some(&Abort::TimedOut(Duration::from_millis(10)))
+        );
     }
 }
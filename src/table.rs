@@ -0,0 +1,94 @@
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+/// A plain text table with left aligned columns.
+/// Rows shorter than the header have their last cell spanning the remaining columns.
+pub struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new<I, S>(header: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            header: header.into_iter().map(Into::into).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        let columns = self.header.len();
+        (0..columns)
+            .map(|i| {
+                std::iter::once(&self.header)
+                    .chain(&self.rows)
+                    // the last cell of a short row spans the remaining columns
+                    .filter(|r| r.len() >= columns || i + 1 < r.len())
+                    .map(|r| r[i].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let widths = self.widths();
+
+        for (i, row) in std::iter::once(&self.header).chain(&self.rows).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &w)| format!("{cell:<w$}"))
+                .join("  ");
+            write!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aligned_columns() {
+        let mut t = Table::new(["Input", "Answer"]);
+        t.push(vec!["07.txt".into(), "6440".into()]);
+        t.push(vec!["07-alice.txt".into(), "5".into()]);
+        assert_eq!(
+            t.to_string(),
+            "Input         Answer\n07.txt        6440\n07-alice.txt  5"
+        );
+    }
+
+    #[test]
+    fn spanning_last_cell() {
+        let mut t = Table::new(["Input", "Parsing", "Answer"]);
+        t.push(vec!["07.txt".into(), "Error: a very long message".into()]);
+        t.push(vec!["07-a.txt".into(), "1ms".into(), "6440".into()]);
+        assert_eq!(
+            t.to_string(),
+            "Input     Parsing  Answer\n07.txt    Error: a very long message\n07-a.txt  1ms      6440"
+        );
+
+        t.push(vec!["07-longer.txt".into(), "Error: too long".into()]);
+        assert_eq!(
+            t.to_string(),
+            "Input          Parsing  Answer\n07.txt         Error: a very long message\n07-a.txt       1ms      6440\n07-longer.txt  Error: too long"
+        );
+    }
+}